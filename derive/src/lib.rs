@@ -3,9 +3,9 @@
 use convert_case::{Case, Casing};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use syn::{parse_macro_input, ItemEnum};
+use syn::{parse_macro_input, ItemEnum, Lit, Meta, NestedMeta, Variant};
 
-#[proc_macro_derive(OnEvent, attributes(unimplemented))]
+#[proc_macro_derive(OnEvent, attributes(event, no_property, synthetic))]
 pub fn derive_on_event(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(item as ItemEnum);
 
@@ -14,12 +14,114 @@ pub fn derive_on_event(item: proc_macro::TokenStream) -> proc_macro::TokenStream
         .into()
 }
 
+/// Options of the `#[event(...)]` variant attribute.
+#[derive(Default)]
+struct EventAttrs {
+    /// DOM event type, e.g. `#[event(name = "pointerdown")]`.
+    name: Option<String>,
+    /// Event interface, e.g. `#[event(interface = "PointerEvent")]`.
+    interface: Option<String>,
+    /// Event targets, e.g. `#[event(target = "element, window")]`.
+    target: Option<Target>,
+}
+
+/// Targets that receive the event.
+#[derive(Clone, Copy)]
+struct Target {
+    element: bool,
+    document: bool,
+    window: bool,
+}
+
+impl Default for Target {
+    fn default() -> Self {
+        Self {
+            element: true,
+            document: false,
+            window: false,
+        }
+    }
+}
+
+impl EventAttrs {
+    fn parse(variant: &Variant) -> syn::Result<Self> {
+        let mut attrs = Self::default();
+
+        for attr in variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("event"))
+        {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(syn::Error::new_spanned(meta, "expected #[event(...)]")),
+            };
+            for nested in list.nested.iter() {
+                match nested {
+                    NestedMeta::Meta(Meta::NameValue(value)) => {
+                        let lit = match &value.lit {
+                            Lit::Str(lit) => lit.value(),
+                            lit => return Err(syn::Error::new_spanned(lit, "expected string")),
+                        };
+                        if value.path.is_ident("name") {
+                            attrs.name = Some(lit);
+                        } else if value.path.is_ident("interface") {
+                            attrs.interface = Some(lit);
+                        } else if value.path.is_ident("target") {
+                            let mut target = Target {
+                                element: false,
+                                document: false,
+                                window: false,
+                            };
+                            for name in lit.split(',').map(str::trim) {
+                                match name {
+                                    "element" => target.element = true,
+                                    "document" => target.document = true,
+                                    "window" => target.window = true,
+                                    _ => {
+                                        return Err(syn::Error::new_spanned(
+                                            value,
+                                            "unknown target",
+                                        ))
+                                    }
+                                }
+                            }
+                            attrs.target = Some(target);
+                        } else {
+                            return Err(syn::Error::new_spanned(value, "unknown option"));
+                        }
+                    }
+                    nested => return Err(syn::Error::new_spanned(nested, "unknown option")),
+                }
+            }
+        }
+
+        Ok(attrs)
+    }
+}
+
+/// Return the DOM name of a `web-sys` interface, which spells
+/// acronyms in CamelCase, e.g. `UIEvent` for `UiEvent`.
+fn dom_interface(interface: &str) -> &str {
+    match interface {
+        "UiEvent" => "UIEvent",
+        interface => interface,
+    }
+}
+
 fn derive_on_event_enum(item: ItemEnum) -> syn::Result<TokenStream> {
+    let mut event_names = vec![];
+    let mut event_interfaces = vec![];
+    let mut from_event_names = vec![];
+    let mut custom_event = None;
     let mut on_event_handlers = vec![];
     let mut set_on_event_handlers = vec![];
     let mut off_event_handlers = vec![];
     let mut event_handlers = vec![];
     let mut collection_event_handlers = vec![];
+    let mut typed_event_handlers = vec![];
+    let mut document_event_handlers = vec![];
+    let mut window_event_handlers = vec![];
 
     let doc1 = item.attrs.iter().filter(|attr| attr.path.is_ident("doc"));
     let doc2 = doc1.clone();
@@ -35,19 +137,93 @@ fn derive_on_event_enum(item: ItemEnum) -> syn::Result<TokenStream> {
         callback: &Closure<dyn FnMut(web_sys::Event)>,
     };
 
-    for variant in item.variants.iter().filter(|variant| {
-        !variant
-            .attrs
-            .iter()
-            .any(|attr| attr.path.is_ident("unimplemented"))
-    }) {
+    for variant in item.variants.iter() {
+        let has_attr = |name| variant.attrs.iter().any(|attr| attr.path.is_ident(name));
         let ident = &variant.ident;
+
+        // Synthetic events are provided by the crate and don't have
+        // a DOM event type.
+        if has_attr("synthetic") {
+            continue;
+        }
+
+        // The variant with a field holds the name of custom events.
+        if !variant.fields.is_empty() {
+            event_names.push(quote! {
+                Event::#ident(name) => Some(name.as_ref()),
+            });
+            custom_event = Some(ident);
+            continue;
+        }
+
+        let attrs = EventAttrs::parse(variant)?;
         let name = ident.to_string().to_case(Case::Snake);
+        let dom_name = attrs
+            .name
+            .unwrap_or_else(|| ident.to_string().to_lowercase());
+
+        event_names.push(quote! {
+            Event::#ident => Some(#dom_name),
+        });
+        from_event_names.push(quote! {
+            #dom_name => Event::#ident,
+        });
+
+        let interface = attrs.interface.unwrap_or_else(|| "Event".to_string());
+        let interface_ident = Ident::new(&interface, Span::call_site());
+        let on_ident = Ident::new(&format!("on_{}", name), Span::call_site());
+        let doc = format!(
+            "Add a `{}` event listener with a `{}` handler.",
+            dom_name, interface
+        );
+        let doc2 = format!(
+            "Events that are not a `{}`, e.g. dispatched by other scripts, are ignored.",
+            interface
+        );
+
+        let dom_interface = dom_interface(&interface);
+        event_interfaces.push(quote! {
+            Event::#ident => #dom_interface,
+        });
+
+        let typed_handler = quote! {
+            #[doc = #doc]
+            #[doc = ""]
+            #[doc = #doc2]
+            pub fn #on_ident<F>(&self, mut handler: F) -> Result<EventListener, Error>
+            where
+                F: FnMut(web_sys::#interface_ident) + 'static,
+            {
+                self.on(Event::#ident, move |event: web_sys::Event| {
+                    if let Ok(event) = event.dyn_into::<web_sys::#interface_ident>() {
+                        handler(event)
+                    }
+                })
+            }
+        };
+        let target = attrs.target.unwrap_or_default();
+        if target.document {
+            document_event_handlers.push(typed_handler.clone());
+        }
+        if target.window {
+            window_event_handlers.push(typed_handler.clone());
+        }
+        if !target.element {
+            continue;
+        }
+        typed_event_handlers.push(typed_handler);
+
+        // Some events can only be handled with event listeners as
+        // they don't have an `on<event>` property.
+        if has_attr("no_property") {
+            continue;
+        }
+
         let get_ident = Ident::new(&name, Span::call_site());
         let set_ident = Ident::new(&format!("set_{}", name), Span::call_site());
-        let name2 = ident.to_string().to_lowercase();
-        let set_ident2 = Ident::new(&format!("set_on{}", name2), Span::call_site());
-        let get_ident2 = Ident::new(&format!("on{}", name2), Span::call_site());
+        let property = dom_name.to_lowercase();
+        let set_ident2 = Ident::new(&format!("set_on{}", property), Span::call_site());
+        let get_ident2 = Ident::new(&format!("on{}", property), Span::call_site());
 
         on_event_handlers.push(quote! {
             Event::#ident => html.#get_ident2().ok_or(Error::EventNotHandled(Event::#ident)),
@@ -84,10 +260,46 @@ fn derive_on_event_enum(item: ItemEnum) -> syn::Result<TokenStream> {
         });
     }
 
+    // `from_name` requires a custom variant for unknown event types.
+    let from_name = custom_event.map(|ident| {
+        quote! {
+            /// Return the event for the DOM event type name.
+            ///
+            /// Unknown names, such as application-specific event
+            /// types, return a custom event.
+            pub fn from_name(name: &str) -> Self {
+                match name {
+                    #(#from_event_names)*
+                    name => Event::#ident(name.to_string().into()),
+                }
+            }
+        }
+    });
+
     Ok(quote! {
+        impl Event {
+            /// Return the DOM event type name, if it is implemented.
+            pub fn name(&self) -> Option<&str> {
+                match self {
+                    #(#event_names)*
+                    _ => None,
+                }
+            }
+
+            /// Return the DOM interface of the event, e.g. `MouseEvent`.
+            pub fn interface(&self) -> &str {
+                match self {
+                    #(#event_interfaces)*
+                    _ => "Event",
+                }
+            }
+
+            #from_name
+        }
+
         #(#doc1)*
         impl Element {
-            pub fn on(&self, event: Event) -> Result<js_sys::Function, Error> {
+            pub fn handler(&self, event: Event) -> Result<js_sys::Function, Error> {
                 #html
 
                 match event {
@@ -115,23 +327,35 @@ fn derive_on_event_enum(item: ItemEnum) -> syn::Result<TokenStream> {
             }
 
             #(#event_handlers)*
+
+            #(#typed_event_handlers)*
         }
 
         #(#doc2)*
         impl Collection {
-            pub fn on(&self, event: Event) -> Result<Vec<js_sys::Function>, Error> {
-                self.0.iter().map(|elem| elem.on(event)).collect::<Result<Vec<_>, _>>()
+            pub fn handler(&self, event: Event) -> Result<Vec<js_sys::Function>, Error> {
+                self.0.iter().map(|elem| elem.handler(event.clone())).collect::<Result<Vec<_>, _>>()
             }
 
             pub fn set_on(&self, event: Event, #callback) {
-                self.0.iter().for_each(|elem| { elem.set_on(event, callback).ok(); })
+                self.0.iter().for_each(|elem| { elem.set_on(event.clone(), callback).ok(); })
             }
 
             pub fn set_off(&self, event: Event) {
-                self.0.iter().for_each(|elem| { elem.set_off(event).ok(); })
+                self.0.iter().for_each(|elem| { elem.set_off(event.clone()).ok(); })
             }
 
-        #(#collection_event_handlers)*
+            #(#collection_event_handlers)*
+
+            #(#typed_event_handlers)*
+        }
+
+        impl Document {
+            #(#document_event_handlers)*
+        }
+
+        impl Window {
+            #(#window_event_handlers)*
         }
     })
 }
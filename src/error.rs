@@ -27,6 +27,9 @@ pub enum Error {
     EventNotHandled(Event),
     /// First element in collection not found
     FirstElementNotFound,
+    /// Invalid keyboard shortcut: '{0}'
+    #[from(ignore)]
+    InvalidShortcut(String),
     /// Failed to cast to HTML element
     NotHtmlElement,
     /// Failed to cast to `EventTarget`
@@ -35,6 +38,12 @@ pub enum Error {
     NoDocumentElement,
     /// `Event` does not have a target element
     NoTargetElement,
+    /// Wrapper without element
+    NoWrapperElement,
+    /// Event type without event
+    NoEventType,
+    /// Event listener was removed
+    ListenerRemoved,
     /// `Element` type does not have '{0}' value
     #[from(ignore)]
     NoValue(&'static str),
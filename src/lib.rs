@@ -8,9 +8,17 @@ mod query;
 
 pub use crate::{
     error::Error,
-    query::{Collection, Document, Element, Event, FormData},
+    query::{
+        debounce, debounce_with, throttle, throttle_with, Collection, Content, Document, Element,
+        ElementBuilder, Event, EventContext, EventListener, EventType, FormData, FormValue,
+        ListenerOptions, Offset, ScrollOptions, Shortcut, ShortcutOptions, Target, TimerOptions,
+        TriggerOptions, Window, CSS_NUMBER,
+    },
 };
 
+#[cfg(feature = "futures")]
+pub use crate::query::{EventStream, NextEvent};
+
 /// `query!` macro to find elements.
 ///
 /// This macro is a helper for the `find` method.
@@ -4,7 +4,9 @@ use crate::{
     error::Error,
     query::{Collection, Element},
 };
-use wasm_bindgen::JsCast;
+use js_sys::Reflect;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::HtmlCollection;
 
 /// Attribute manipulation methods
 impl Element {
@@ -47,7 +49,35 @@ impl Element {
         self.0.set_inner_html(html)
     }
 
-    // TODO: .prop()
+    /// Return the property of the underlying JS object.
+    ///
+    /// Unlike attributes, properties reflect the current state of
+    /// the element, e.g. `checked`, `selectedIndex` or
+    /// `indeterminate`.
+    pub fn prop(&self, key: &str) -> Result<JsValue, Error> {
+        Reflect::get(&self.0, &key.into()).map_err(Into::into)
+    }
+
+    /// Return the property if it is a boolean.
+    pub fn prop_bool(&self, key: &str) -> Option<bool> {
+        self.prop(key).ok()?.as_bool()
+    }
+
+    /// Return the property if it is a string.
+    pub fn prop_str(&self, key: &str) -> Option<String> {
+        self.prop(key).ok()?.as_string()
+    }
+
+    /// Return the property if it is a number.
+    pub fn prop_f64(&self, key: &str) -> Option<f64> {
+        self.prop(key).ok()?.as_f64()
+    }
+
+    /// Set the property of the underlying JS object.
+    pub fn set_prop(&self, key: &str, value: impl Into<JsValue>) -> Result<(), Error> {
+        Reflect::set(&self.0, &key.into(), &value.into())?;
+        Ok(())
+    }
 
     /// Remove the attribute.
     pub fn remove_attr(&self, key: &str) -> Result<(), Error> {
@@ -63,101 +93,114 @@ impl Element {
         self.0.class_list().remove_1(class).map_err(Into::into)
     }
 
-    // TODO: .remove_prop()
+    /// Delete the property of the underlying JS object.
+    ///
+    /// Only properties that were added to the object can be removed,
+    /// built-in properties are inherited from the prototype.
+    pub fn remove_prop(&self, key: &str) -> Result<(), Error> {
+        Reflect::delete_property(self.0.unchecked_ref::<js_sys::Object>(), &key.into())?;
+        Ok(())
+    }
 
     /// Return the matching attribute, if found.
     pub fn toggle_class(&self, class: &str) -> Result<bool, Error> {
         self.0.class_list().toggle(class).map_err(Into::into)
     }
 
+    /// Return the value of the element.
+    ///
+    /// This reads the `value` property of any element that has one,
+    /// including form-associated custom elements.  Numeric values,
+    /// such as the value of `<li>`, `<meter>` or `<progress>`, are
+    /// returned as a string.
     pub fn val(&self) -> Result<String, Error> {
-        // TODO: there must be a nicer and generic way to figure out
-        // if the Node has a `value` property.  jQuery looks for the
-        // value property or function but I'm not sure if this can be
-        // done in Rust on the raw `JsValue` object.
-        if let Some(node) = self.0.dyn_ref::<web_sys::HtmlButtonElement>() {
-            Ok(node.value())
-        } else if let Some(node) = self.0.dyn_ref::<web_sys::HtmlDataElement>() {
-            Ok(node.value())
-        } else if let Some(node) = self.0.dyn_ref::<web_sys::HtmlInputElement>() {
-            Ok(node.value())
-        } else if let Some(node) = self.0.dyn_ref::<web_sys::HtmlOptionElement>() {
-            Ok(node.value())
-        } else if let Some(node) = self.0.dyn_ref::<web_sys::HtmlOutputElement>() {
-            Ok(node.value())
-        } else if let Some(node) = self.0.dyn_ref::<web_sys::HtmlParamElement>() {
-            Ok(node.value())
-        } else if let Some(node) = self.0.dyn_ref::<web_sys::HtmlSelectElement>() {
-            Ok(node.value())
-        } else if let Some(node) = self.0.dyn_ref::<web_sys::HtmlTextAreaElement>() {
-            Ok(node.value())
-        } else {
-            Err(Error::NoValue("string"))
-        }
+        let value = self.value().ok_or(Error::NoValue("string"))?;
+        value
+            .as_string()
+            .or_else(|| value.as_f64().map(|value| value.to_string()))
+            .ok_or(Error::NoValue("string"))
     }
 
+    /// Return the value of the element as a number.
     pub fn val_f64(&self) -> Result<f64, Error> {
-        if let Some(node) = self.0.dyn_ref::<web_sys::HtmlMeterElement>() {
-            Ok(node.value())
-        } else if let Some(node) = self.0.dyn_ref::<web_sys::HtmlProgressElement>() {
-            Ok(node.value())
-        } else {
-            Err(Error::NoValue("float"))
-        }
+        let value = self.value().ok_or(Error::NoValue("float"))?;
+        value
+            .as_f64()
+            .or_else(|| value.as_string()?.trim().parse().ok())
+            .ok_or(Error::NoValue("float"))
     }
 
+    /// Return the value of the element as an integer.
     pub fn val_i32(&self) -> Result<i32, Error> {
-        if let Some(node) = self.0.dyn_ref::<web_sys::HtmlLiElement>() {
-            Ok(node.value())
-        } else {
-            Err(Error::NoValue("float"))
-        }
+        let value = self.value().ok_or(Error::NoValue("integer"))?;
+        value
+            .as_f64()
+            .map(|value| value as i32)
+            .or_else(|| value.as_string()?.trim().parse().ok())
+            .ok_or(Error::NoValue("integer"))
     }
 
-    pub fn set_val(&self, value: &str) -> Result<(), Error> {
-        if let Some(node) = self.0.dyn_ref::<web_sys::HtmlButtonElement>() {
-            node.set_value(value)
-        } else if let Some(node) = self.0.dyn_ref::<web_sys::HtmlDataElement>() {
-            node.set_value(value)
-        } else if let Some(node) = self.0.dyn_ref::<web_sys::HtmlInputElement>() {
-            node.set_value(value)
-        } else if let Some(node) = self.0.dyn_ref::<web_sys::HtmlOptionElement>() {
-            node.set_value(value)
-        } else if let Some(node) = self.0.dyn_ref::<web_sys::HtmlOutputElement>() {
-            node.set_value(value)
-        } else if let Some(node) = self.0.dyn_ref::<web_sys::HtmlParamElement>() {
-            node.set_value(value)
-        } else if let Some(node) = self.0.dyn_ref::<web_sys::HtmlSelectElement>() {
-            node.set_value(value)
-        } else if let Some(node) = self.0.dyn_ref::<web_sys::HtmlTextAreaElement>() {
-            node.set_value(value)
-        } else {
-            return Err(Error::NoValue("string"));
+    /// Return the values of all selected options.
+    ///
+    /// Unlike `val`, which only returns the first selected option,
+    /// this returns all values of a `<select multiple>` element.
+    /// Other elements return their value as a single entry.
+    pub fn val_multiple(&self) -> Result<Vec<String>, Error> {
+        match self.prop("selectedOptions")?.dyn_into::<HtmlCollection>() {
+            Ok(options) => Collection::from(options)
+                .0
+                .iter()
+                .map(Element::val)
+                .collect(),
+            Err(_) => self.val().map(|value| vec![value]),
         }
+    }
 
-        Ok(())
+    /// Set the value of the element.
+    pub fn set_val(&self, value: &str) -> Result<(), Error> {
+        self.set_value(value.into(), "string")
     }
 
+    /// Set the value of the element as a number.
     pub fn set_val_f64(&self, value: f64) -> Result<(), Error> {
-        if let Some(node) = self.0.dyn_ref::<web_sys::HtmlMeterElement>() {
-            node.set_value(value)
-        } else if let Some(node) = self.0.dyn_ref::<web_sys::HtmlProgressElement>() {
-            node.set_value(value)
-        } else {
-            return Err(Error::NoValue("float"));
+        self.set_value(value.into(), "float")
+    }
+
+    /// Set the value of the element as an integer.
+    pub fn set_val_i32(&self, value: i32) -> Result<(), Error> {
+        self.set_value(value.into(), "integer")
+    }
+
+    /// Select all options of a `<select>` element that match one of
+    /// the values and deselect the others.
+    pub fn set_val_multiple(&self, values: &[&str]) -> Result<(), Error> {
+        let options = self
+            .prop("options")?
+            .dyn_into::<HtmlCollection>()
+            .map_err(|_| Error::NoValue("options"))?;
+        for option in Collection::from(options).0.iter() {
+            let selected = values.contains(&option.val()?.as_str());
+            option.set_prop("selected", selected)?;
         }
 
         Ok(())
     }
 
-    pub fn set_val_i32(&self, value: i32) -> Result<(), Error> {
-        if let Some(node) = self.0.dyn_ref::<web_sys::HtmlLiElement>() {
-            node.set_value(value)
+    /// Return the `value` property if the element has one.
+    fn value(&self) -> Option<JsValue> {
+        if Reflect::has(&self.0, &"value".into()).ok()? {
+            self.prop("value").ok()
         } else {
-            return Err(Error::NoValue("float"));
+            None
         }
+    }
 
-        Ok(())
+    /// Set the `value` property if the element has one.
+    fn set_value(&self, value: JsValue, kind: &'static str) -> Result<(), Error> {
+        if self.value().is_none() {
+            return Err(Error::NoValue(kind));
+        }
+        self.set_prop("value", value)
     }
 }
 
@@ -195,6 +238,22 @@ impl Collection {
         self.0.iter().for_each(|elem| elem.set_html(html))
     }
 
+    pub fn prop(&self, key: &str) -> Vec<JsValue> {
+        self.0
+            .iter()
+            .filter_map(|elem| elem.prop(key).ok())
+            .collect()
+    }
+
+    pub fn set_prop(&self, key: &str, value: impl Into<JsValue>) -> Result<(), Error> {
+        let value = value.into();
+        for element in self.0.iter() {
+            element.set_prop(key, value.clone())?;
+        }
+
+        Ok(())
+    }
+
     pub fn remove_attr(&self, key: &str) -> Result<(), Error> {
         for element in self.0.iter() {
             element.remove_attr(key)?;
@@ -211,6 +270,14 @@ impl Collection {
         Ok(())
     }
 
+    pub fn remove_prop(&self, key: &str) -> Result<(), Error> {
+        for element in self.0.iter() {
+            element.remove_prop(key)?;
+        }
+
+        Ok(())
+    }
+
     /// Return the matching attribute, if found.
     pub fn toggle_class(&self, class: &str) -> Result<(), Error> {
         for element in self.0.iter() {
@@ -241,6 +308,13 @@ impl Collection {
             .collect()
     }
 
+    pub fn val_multiple(&self) -> Vec<Vec<String>> {
+        self.0
+            .iter()
+            .map(|elem| elem.val_multiple().unwrap_or_default())
+            .collect()
+    }
+
     pub fn set_val(&self, value: &str) {
         self.0.iter().for_each(|elem| {
             elem.set_val(value).ok();
@@ -258,4 +332,10 @@ impl Collection {
             elem.set_val_i32(value).ok();
         });
     }
+
+    pub fn set_val_multiple(&self, values: &[&str]) {
+        self.0.iter().for_each(|elem| {
+            elem.set_val_multiple(values).ok();
+        });
+    }
 }
@@ -0,0 +1,98 @@
+//! Element builder
+
+use crate::{
+    error::Error,
+    query::{Content, Document, Element},
+};
+
+/// Builder of new elements.
+///
+/// # Examples
+///
+/// ```rust
+/// use web_sys_query::{Element, Error};
+///
+/// fn item() -> Result<Element, Error> {
+///     Element::build("li")
+///         .class("item")
+///         .attr("data-id", "3")
+///         .text("hi")
+///         .build()
+/// }
+/// # fn main() {}
+/// ```
+#[derive(Debug)]
+#[must_use = "the element is returned by build"]
+pub struct ElementBuilder(Result<Element, Error>);
+
+impl ElementBuilder {
+    /// Set the `id` attribute.
+    pub fn id(self, id: &str) -> Self {
+        self.attr("id", id)
+    }
+
+    /// Add a class.
+    pub fn class(self, class: &str) -> Self {
+        self.and_then(|element| element.add_class(class))
+    }
+
+    /// Set an attribute.
+    pub fn attr(self, key: &str, value: &str) -> Self {
+        self.and_then(|element| element.set_attr(key, value))
+    }
+
+    /// Set the text content.
+    pub fn text(self, text: &str) -> Self {
+        self.and_then(|element| {
+            element.set_text_content(Some(text));
+            Ok(())
+        })
+    }
+
+    /// Set the inner HTML.
+    pub fn html(self, html: &str) -> Self {
+        self.and_then(|element| {
+            element.set_html(html);
+            Ok(())
+        })
+    }
+
+    /// Append a child element, collection, HTML or text.
+    pub fn child<C: Into<Content>>(self, content: C) -> Self {
+        self.and_then(|element| element.append(content))
+    }
+
+    /// Return the element or the first error of the builder.
+    pub fn build(self) -> Result<Element, Error> {
+        self.0
+    }
+
+    fn and_then<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&Element) -> Result<(), Error>,
+    {
+        Self(self.0.and_then(|element| f(&element).map(|_| element)))
+    }
+}
+
+impl Element {
+    /// Build a new element with the tag name in the current document.
+    pub fn build(tag: &str) -> ElementBuilder {
+        match Document::new() {
+            Ok(document) => document.build(tag),
+            Err(err) => ElementBuilder(Err(err)),
+        }
+    }
+}
+
+impl Document {
+    /// Build a new element with the tag name in the document.
+    pub fn build(&self, tag: &str) -> ElementBuilder {
+        ElementBuilder(
+            self.0
+                .create_element(tag)
+                .map(Into::into)
+                .map_err(Into::into),
+        )
+    }
+}
@@ -0,0 +1,164 @@
+//! Event context
+
+use crate::{error::Error, query::Element};
+use std::{convert::TryFrom, ops::Deref};
+use wasm_bindgen::JsCast;
+use web_sys::{KeyboardEvent, MouseEvent};
+
+/// Event with jQuery-like methods.
+///
+/// The context wraps a DOM event and provides access to the target
+/// elements and the most common keyboard and mouse properties
+/// without casting the event to its interface first.
+#[derive(Clone, Debug)]
+pub struct EventContext {
+    event: web_sys::Event,
+    current_target: Option<Element>,
+}
+
+impl EventContext {
+    /// Create a context with a different current target, e.g. the
+    /// matching element of a delegated event.
+    pub(crate) fn with_current_target(event: web_sys::Event, current_target: Element) -> Self {
+        Self {
+            event,
+            current_target: Some(current_target),
+        }
+    }
+
+    /// Return the DOM event.
+    pub fn event(&self) -> &web_sys::Event {
+        &self.event
+    }
+
+    /// Return the element that dispatched the event.
+    pub fn target(&self) -> Result<Element, Error> {
+        Element::try_from(self.event.clone())
+    }
+
+    /// Return the element that is currently handling the event.
+    ///
+    /// For delegated events, this is the element that matched the
+    /// selectors instead of the element of the listener.
+    pub fn current_target(&self) -> Option<Element> {
+        self.current_target.clone().or_else(|| {
+            self.event
+                .current_target()
+                .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+                .map(Into::into)
+        })
+    }
+
+    /// Prevent the default action of the event.
+    pub fn prevent_default(&self) {
+        self.event.prevent_default()
+    }
+
+    /// Check if the default action of the event was prevented.
+    pub fn is_default_prevented(&self) -> bool {
+        self.event.default_prevented()
+    }
+
+    /// Stop the event from bubbling up the DOM.
+    pub fn stop_propagation(&self) {
+        self.event.stop_propagation()
+    }
+
+    /// Stop the event from bubbling and calling other handlers.
+    pub fn stop_immediate_propagation(&self) {
+        self.event.stop_immediate_propagation()
+    }
+
+    /// Return the key value of keyboard events.
+    pub fn key(&self) -> Option<String> {
+        self.keyboard().map(KeyboardEvent::key)
+    }
+
+    /// Return the physical key code of keyboard events.
+    pub fn code(&self) -> Option<String> {
+        self.keyboard().map(KeyboardEvent::code)
+    }
+
+    /// Check if the Alt key was pressed.
+    pub fn alt_key(&self) -> bool {
+        self.modifier(KeyboardEvent::alt_key, MouseEvent::alt_key)
+    }
+
+    /// Check if the Control key was pressed.
+    pub fn ctrl_key(&self) -> bool {
+        self.modifier(KeyboardEvent::ctrl_key, MouseEvent::ctrl_key)
+    }
+
+    /// Check if the Meta key, e.g. Command on macOS, was pressed.
+    pub fn meta_key(&self) -> bool {
+        self.modifier(KeyboardEvent::meta_key, MouseEvent::meta_key)
+    }
+
+    /// Check if the Shift key was pressed.
+    pub fn shift_key(&self) -> bool {
+        self.modifier(KeyboardEvent::shift_key, MouseEvent::shift_key)
+    }
+
+    /// Return the mouse button of mouse events.
+    pub fn button(&self) -> Option<i16> {
+        self.mouse().map(MouseEvent::button)
+    }
+
+    /// Return the mouse position relative to the viewport.
+    pub fn client_xy(&self) -> Option<(i32, i32)> {
+        self.mouse()
+            .map(|event| (event.client_x(), event.client_y()))
+    }
+
+    /// Return the mouse position relative to the document.
+    pub fn page_xy(&self) -> Option<(i32, i32)> {
+        self.mouse().map(|event| (event.page_x(), event.page_y()))
+    }
+
+    /// Cast the event to a specific event interface.
+    pub fn dyn_ref<T: JsCast>(&self) -> Result<&T, Error> {
+        self.event.dyn_ref::<T>().ok_or(Error::DynRefFailed)
+    }
+
+    fn keyboard(&self) -> Option<&KeyboardEvent> {
+        self.event.dyn_ref::<KeyboardEvent>()
+    }
+
+    fn mouse(&self) -> Option<&MouseEvent> {
+        self.event.dyn_ref::<MouseEvent>()
+    }
+
+    fn modifier<K, M>(&self, keyboard: K, mouse: M) -> bool
+    where
+        K: Fn(&KeyboardEvent) -> bool,
+        M: Fn(&MouseEvent) -> bool,
+    {
+        self.keyboard()
+            .map(keyboard)
+            .or_else(|| self.mouse().map(mouse))
+            .unwrap_or_default()
+    }
+}
+
+impl Deref for EventContext {
+    type Target = web_sys::Event;
+
+    fn deref(&self) -> &Self::Target {
+        &self.event
+    }
+}
+
+impl From<web_sys::Event> for EventContext {
+    fn from(event: web_sys::Event) -> Self {
+        Self {
+            event,
+            current_target: None,
+        }
+    }
+}
+
+impl From<EventContext> for web_sys::Event {
+    fn from(context: EventContext) -> Self {
+        context.event
+    }
+}
@@ -0,0 +1,171 @@
+//! CSS
+
+use crate::{
+    error::Error,
+    query::{Collection, Element},
+};
+use web_sys::CssStyleDeclaration;
+
+/// CSS properties that are not suffixed with `px` if the value is a
+/// number, like jQuery's `$.cssNumber`.
+pub const CSS_NUMBER: &[&str] = &[
+    "animation-iteration-count",
+    "aspect-ratio",
+    "border-image-slice",
+    "column-count",
+    "fill-opacity",
+    "flex",
+    "flex-grow",
+    "flex-shrink",
+    "flood-opacity",
+    "font-weight",
+    "grid-area",
+    "grid-column",
+    "grid-column-end",
+    "grid-column-start",
+    "grid-row",
+    "grid-row-end",
+    "grid-row-start",
+    "line-height",
+    "opacity",
+    "order",
+    "orphans",
+    "scale",
+    "stop-opacity",
+    "stroke-miterlimit",
+    "stroke-opacity",
+    "widows",
+    "z-index",
+    "zoom",
+];
+
+/// CSS methods
+impl Element {
+    /// Return the computed value of the CSS property.
+    ///
+    /// The property can be in CSS or camel case, e.g. `z-index` or
+    /// `zIndex`.  The inline style is returned if the element does
+    /// not have a computed style, for example if it is not attached
+    /// to a rendered document.
+    pub fn css(&self, property: &str) -> Result<String, Error> {
+        let property = css_property(property);
+        let value = self
+            .computed_style()?
+            .map(|style| style.get_property_value(&property))
+            .transpose()?
+            .unwrap_or_default();
+        if !value.is_empty() {
+            return Ok(value);
+        }
+        self.style()?
+            .get_property_value(&property)
+            .map_err(Into::into)
+    }
+
+    /// Set the inline value of the CSS property.
+    ///
+    /// Numbers are suffixed with `px` unless the property is listed
+    /// in `CSS_NUMBER`.  An empty value removes the property.
+    pub fn set_css(&self, property: &str, value: &str) -> Result<(), Error> {
+        let property = css_property(property);
+        let value = css_value(&property, value);
+        self.style()?
+            .set_property(&property, &value)
+            .map_err(Into::into)
+    }
+
+    /// Set multiple CSS properties.
+    pub fn set_css_map<I, K, V>(&self, properties: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        for (property, value) in properties {
+            self.set_css(property.as_ref(), value.as_ref())?;
+        }
+
+        Ok(())
+    }
+
+    /// Return the inline style declaration of the element.
+    pub(crate) fn style(&self) -> Result<CssStyleDeclaration, Error> {
+        Ok(self.dyn_ref::<web_sys::HtmlElement>()?.style())
+    }
+
+    /// Return the computed style of the element.
+    pub(crate) fn computed_style(&self) -> Result<Option<CssStyleDeclaration>, Error> {
+        let window = self
+            .owner_document()
+            .and_then(|document| document.default_view())
+            .or_else(web_sys::window)
+            .ok_or(Error::DomElementNotFound("window"))?;
+        window.get_computed_style(&self.0).map_err(Into::into)
+    }
+}
+
+/// CSS methods
+impl Collection {
+    pub fn css(&self, property: &str) -> Vec<String> {
+        self.0
+            .iter()
+            .filter_map(|elem| elem.css(property).ok())
+            .collect()
+    }
+
+    pub fn set_css(&self, property: &str, value: &str) -> Result<(), Error> {
+        for element in self.0.iter() {
+            element.set_css(property, value)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn set_css_map<I, K, V>(&self, properties: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let properties = properties
+            .into_iter()
+            .map(|(k, v)| (k.as_ref().to_string(), v.as_ref().to_string()))
+            .collect::<Vec<_>>();
+        for element in self.0.iter() {
+            element.set_css_map(properties.iter().map(|(k, v)| (k, v)))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Convert a camel case property name to CSS, e.g. `zIndex` to `z-index`.
+fn css_property(property: &str) -> String {
+    if property.starts_with("--") {
+        return property.to_string();
+    }
+    let mut result = String::with_capacity(property.len() + 2);
+    for c in property.chars() {
+        if c.is_ascii_uppercase() {
+            result.push('-');
+            result.push(c.to_ascii_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Add the `px` suffix to numbers.
+fn css_value(property: &str, value: &str) -> String {
+    let value = value.trim();
+    if !value.is_empty()
+        && value.parse::<f64>().is_ok()
+        && !property.starts_with("--")
+        && !CSS_NUMBER.contains(&property)
+    {
+        format!("{}px", value)
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,87 @@
+//! Element data
+
+use crate::query::{Collection, Element};
+use js_sys::{Map, WeakMap};
+use wasm_bindgen::{JsCast, JsValue};
+
+thread_local! {
+    /// Stored values of the elements.
+    ///
+    /// The `WeakMap` does not keep the elements alive, the values
+    /// are dropped with elements that are removed from the DOM.
+    static STORE: WeakMap = WeakMap::new();
+}
+
+/// Return the map of stored values of the element.
+fn values(element: &Element) -> Option<Map> {
+    STORE
+        .with(|store| store.get(element.0.unchecked_ref()))
+        .dyn_into::<Map>()
+        .ok()
+}
+
+/// Data methods
+impl Element {
+    /// Return the stored value of the key.
+    ///
+    /// If no value was stored, the `data-<key>` attribute is
+    /// returned as a string.
+    pub fn data(&self, key: &str) -> Option<JsValue> {
+        let name = JsValue::from(key);
+        values(self)
+            .filter(|values| values.has(&name))
+            .map(|values| values.get(&name))
+            .or_else(|| self.attr(&format!("data-{}", key)).map(Into::into))
+    }
+
+    /// Store a value of the key with the element.
+    pub fn set_data(&self, key: &str, value: impl Into<JsValue>) {
+        let values = values(self).unwrap_or_else(|| {
+            let values = Map::new();
+            STORE.with(|store| store.set(self.0.unchecked_ref(), &values));
+            values
+        });
+        values.set(&key.into(), &value.into());
+    }
+
+    /// Remove the stored value of the key, or all values if `None`.
+    pub fn remove_data(&self, key: Option<&str>) {
+        if let Some(values) = values(self) {
+            if let Some(key) = key {
+                values.delete(&key.into());
+            }
+            if key.is_none() || values.size() == 0 {
+                STORE.with(|store| store.delete(self.0.unchecked_ref()));
+            }
+        }
+    }
+}
+
+/// Copy all stored values of an element to another element.
+pub(crate) fn copy(from: &Element, to: &Element) {
+    if let Some(values) = values(from) {
+        values.for_each(&mut |value, key| {
+            if let Some(key) = key.as_string() {
+                to.set_data(&key, value);
+            }
+        });
+    }
+}
+
+/// Data methods
+impl Collection {
+    pub fn data(&self, key: &str) -> Vec<JsValue> {
+        self.0.iter().filter_map(|elem| elem.data(key)).collect()
+    }
+
+    pub fn set_data(&self, key: &str, value: impl Into<JsValue>) {
+        let value = value.into();
+        self.0
+            .iter()
+            .for_each(|elem| elem.set_data(key, value.clone()))
+    }
+
+    pub fn remove_data(&self, key: Option<&str>) {
+        self.0.iter().for_each(|elem| elem.remove_data(key))
+    }
+}
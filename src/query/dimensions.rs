@@ -0,0 +1,219 @@
+//! Dimensions
+
+use crate::{
+    error::Error,
+    query::{Collection, Document, Element, Window},
+};
+
+/// Horizontal or vertical dimension.
+#[derive(Clone, Copy)]
+enum Axis {
+    Width,
+    Height,
+}
+
+impl Axis {
+    fn sides(self) -> [&'static str; 2] {
+        match self {
+            Axis::Width => ["left", "right"],
+            Axis::Height => ["top", "bottom"],
+        }
+    }
+}
+
+/// Dimensions
+impl Element {
+    /// Return the width of the content.
+    pub fn width(&self) -> f64 {
+        self.dimension(Axis::Width, &["padding", "border"])
+    }
+
+    /// Return the height of the content.
+    pub fn height(&self) -> f64 {
+        self.dimension(Axis::Height, &["padding", "border"])
+    }
+
+    /// Return the width including the padding.
+    pub fn inner_width(&self) -> f64 {
+        self.dimension(Axis::Width, &["border"])
+    }
+
+    /// Return the height including the padding.
+    pub fn inner_height(&self) -> f64 {
+        self.dimension(Axis::Height, &["border"])
+    }
+
+    /// Return the width including the padding and border, and
+    /// optionally the margin.
+    pub fn outer_width(&self, margin: bool) -> f64 {
+        self.outer(Axis::Width, margin)
+    }
+
+    /// Return the height including the padding and border, and
+    /// optionally the margin.
+    pub fn outer_height(&self, margin: bool) -> f64 {
+        self.outer(Axis::Height, margin)
+    }
+
+    /// Set the width of the content.
+    ///
+    /// The style width includes the padding and border if the
+    /// element's `box-sizing` is `border-box`.
+    pub fn set_width(&self, width: f64) -> Result<(), Error> {
+        self.set_dimension(Axis::Width, width)
+    }
+
+    /// Set the height of the content.
+    pub fn set_height(&self, height: f64) -> Result<(), Error> {
+        self.set_dimension(Axis::Height, height)
+    }
+
+    /// Return the border box size without the edges.
+    fn dimension(&self, axis: Axis, edges: &[&str]) -> f64 {
+        (self.outer(axis, false) - self.edges(axis, edges)).max(0.0)
+    }
+
+    /// Return the border box size with the optional margin.
+    fn outer(&self, axis: Axis, margin: bool) -> f64 {
+        let rect = self.get_bounding_client_rect();
+        let size = match axis {
+            Axis::Width => rect.width(),
+            Axis::Height => rect.height(),
+        };
+        if margin {
+            size + self.edges(axis, &["margin"])
+        } else {
+            size
+        }
+    }
+
+    fn set_dimension(&self, axis: Axis, size: f64) -> Result<(), Error> {
+        let size = if self.css("box-sizing").ok().as_deref() == Some("border-box") {
+            size + self.edges(axis, &["padding", "border"])
+        } else {
+            size
+        };
+        let property = match axis {
+            Axis::Width => "width",
+            Axis::Height => "height",
+        };
+        self.set_css(property, &size.max(0.0).to_string())
+    }
+
+    /// Return the sum of the computed edges, e.g. padding and border.
+    fn edges(&self, axis: Axis, edges: &[&str]) -> f64 {
+        let style = match self.computed_style() {
+            Ok(Some(style)) => style,
+            _ => return 0.0,
+        };
+        let mut size = 0.0;
+        for edge in edges {
+            for side in axis.sides().iter() {
+                let property = match *edge {
+                    "border" => format!("border-{}-width", side),
+                    edge => format!("{}-{}", edge, side),
+                };
+                size += style
+                    .get_property_value(&property)
+                    .ok()
+                    .and_then(|value| value.trim_end_matches("px").parse::<f64>().ok())
+                    .unwrap_or_default();
+            }
+        }
+        size
+    }
+}
+
+/// Dimensions
+impl Collection {
+    pub fn width(&self) -> Vec<f64> {
+        self.0.iter().map(|elem| elem.width()).collect()
+    }
+
+    pub fn height(&self) -> Vec<f64> {
+        self.0.iter().map(|elem| elem.height()).collect()
+    }
+
+    pub fn inner_width(&self) -> Vec<f64> {
+        self.0.iter().map(|elem| elem.inner_width()).collect()
+    }
+
+    pub fn inner_height(&self) -> Vec<f64> {
+        self.0.iter().map(|elem| elem.inner_height()).collect()
+    }
+
+    pub fn outer_width(&self, margin: bool) -> Vec<f64> {
+        self.0.iter().map(|elem| elem.outer_width(margin)).collect()
+    }
+
+    pub fn outer_height(&self, margin: bool) -> Vec<f64> {
+        self.0
+            .iter()
+            .map(|elem| elem.outer_height(margin))
+            .collect()
+    }
+
+    pub fn set_width(&self, width: f64) -> Result<(), Error> {
+        for element in self.0.iter() {
+            element.set_width(width)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn set_height(&self, height: f64) -> Result<(), Error> {
+        for element in self.0.iter() {
+            element.set_height(height)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Dimensions
+impl Document {
+    /// Return the width of the whole document.
+    pub fn width(&self) -> f64 {
+        self.scroll_size(|elem| [elem.scroll_width(), elem.client_width()])
+    }
+
+    /// Return the height of the whole document.
+    pub fn height(&self) -> f64 {
+        self.scroll_size(|elem| [elem.scroll_height(), elem.client_height()])
+    }
+
+    fn scroll_size<F>(&self, f: F) -> f64
+    where
+        F: Fn(&web_sys::Element) -> [i32; 2],
+    {
+        let body = self.0.body().map(Into::into);
+        self.0
+            .document_element()
+            .into_iter()
+            .chain(body)
+            .flat_map(|elem| f(&elem).to_vec())
+            .max()
+            .unwrap_or_default() as f64
+    }
+}
+
+/// Dimensions
+impl Window {
+    /// Return the width of the viewport without the scrollbar.
+    pub fn width(&self) -> f64 {
+        self.document_element()
+            .map(|elem| elem.client_width())
+            .unwrap_or_default() as f64
+    }
+
+    /// Return the height of the viewport without the scrollbar.
+    pub fn height(&self) -> f64 {
+        self.document_element()
+            .map(|elem| elem.client_height())
+            .unwrap_or_default() as f64
+    }
+
+    fn document_element(&self) -> Option<web_sys::Element> {
+        self.0.document().and_then(|doc| doc.document_element())
+    }
+}
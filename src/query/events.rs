@@ -2,41 +2,989 @@
 
 use crate::{
     error::Error,
-    query::{Collection, Element},
+    query::{
+        listener, Collection, Document, Element, EventContext, EventListener, ListenerOptions,
+        Window,
+    },
 };
-use wasm_bindgen::{closure::Closure, JsCast};
+use js_sys::{Array, Function, Reflect};
+use std::{borrow::Cow, convert::TryFrom, rc::Rc};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::EventTarget;
 use web_sys_query_derive::OnEvent;
 
 /// Event handlers
-#[derive(Copy, Clone, Debug, OnEvent)]
+#[derive(Clone, Debug, Eq, OnEvent, PartialEq)]
 pub enum Event {
+    #[event(name = "animationcancel", interface = "AnimationEvent")]
+    AnimationCancel,
+    #[event(name = "animationend", interface = "AnimationEvent")]
+    AnimationEnd,
+    #[event(name = "animationiteration", interface = "AnimationEvent")]
+    AnimationIteration,
+    #[event(name = "animationstart", interface = "AnimationEvent")]
+    AnimationStart,
+    #[event(
+        name = "beforeunload",
+        interface = "BeforeUnloadEvent",
+        target = "window"
+    )]
+    BeforeUnload,
+    #[event(interface = "FocusEvent")]
     Blur,
     Change,
+    #[event(interface = "MouseEvent")]
     Click,
+    #[event(interface = "MouseEvent")]
     ContextMenu,
+    /// Custom or any other DOM event type.
+    Custom(Cow<'static, str>),
+    #[event(interface = "MouseEvent")]
     DblClick,
+    #[event(name = "DOMContentLoaded", target = "document")]
+    DomContentLoaded,
+    #[event(name = "drag", interface = "DragEvent")]
+    Drag,
+    #[event(name = "dragend", interface = "DragEvent")]
+    DragEnd,
+    #[event(name = "dragenter", interface = "DragEvent")]
+    DragEnter,
+    #[event(name = "dragleave", interface = "DragEvent")]
+    DragLeave,
+    #[event(name = "dragover", interface = "DragEvent")]
+    DragOver,
+    #[event(name = "dragstart", interface = "DragEvent")]
+    DragStart,
+    #[event(name = "drop", interface = "DragEvent")]
+    Drop,
+    #[event(interface = "FocusEvent")]
     Focus,
-    #[unimplemented]
+    #[no_property]
+    #[event(interface = "FocusEvent")]
     FocusIn,
-    #[unimplemented]
+    #[no_property]
+    #[event(interface = "FocusEvent")]
     FocusOut,
-    #[unimplemented]
+    #[event(name = "hashchange", interface = "HashChangeEvent", target = "window")]
+    HashChange,
+    #[synthetic]
     Hover,
+    #[event(name = "input", interface = "InputEvent")]
+    Input,
+    #[event(interface = "KeyboardEvent", target = "element, document")]
     KeyDown,
+    #[event(interface = "KeyboardEvent", target = "element, document")]
     KeyPress,
+    #[event(interface = "KeyboardEvent", target = "element, document")]
     KeyUp,
+    #[event(target = "element, window")]
     Load,
+    #[event(name = "message", interface = "MessageEvent", target = "window")]
+    Message,
+    #[event(interface = "MouseEvent")]
     MouseDown,
+    #[event(interface = "MouseEvent")]
     MouseEnter,
+    #[event(interface = "MouseEvent")]
     MouseLeave,
+    #[event(interface = "MouseEvent")]
     MouseMove,
+    #[event(interface = "MouseEvent")]
     MouseOut,
+    #[event(interface = "MouseEvent")]
     MouseOver,
+    #[event(interface = "MouseEvent")]
     MouseUp,
-    #[unimplemented]
+    #[event(name = "offline", target = "window")]
+    Offline,
+    #[event(name = "online", target = "window")]
+    Online,
+    #[event(name = "pointercancel", interface = "PointerEvent")]
+    PointerCancel,
+    #[event(name = "pointerdown", interface = "PointerEvent")]
+    PointerDown,
+    #[event(name = "pointerenter", interface = "PointerEvent")]
+    PointerEnter,
+    #[event(name = "pointerleave", interface = "PointerEvent")]
+    PointerLeave,
+    #[event(name = "pointermove", interface = "PointerEvent")]
+    PointerMove,
+    #[event(name = "pointerout", interface = "PointerEvent")]
+    PointerOut,
+    #[event(name = "pointerover", interface = "PointerEvent")]
+    PointerOver,
+    #[event(name = "pointerup", interface = "PointerEvent")]
+    PointerUp,
+    #[event(name = "popstate", interface = "PopStateEvent", target = "window")]
+    PopState,
+    #[synthetic]
     Ready,
+    #[event(interface = "UiEvent", target = "element, window")]
     Resize,
+    #[event(target = "element, document, window")]
     Scroll,
     Select,
+    #[event(name = "selectionchange", target = "document")]
+    SelectionChange,
+    #[event(name = "storage", interface = "StorageEvent", target = "window")]
+    Storage,
+    #[event(interface = "SubmitEvent")]
     Submit,
+    #[event(name = "touchcancel", interface = "TouchEvent")]
+    TouchCancel,
+    #[event(name = "touchend", interface = "TouchEvent")]
+    TouchEnd,
+    #[event(name = "touchmove", interface = "TouchEvent")]
+    TouchMove,
+    #[event(name = "touchstart", interface = "TouchEvent")]
+    TouchStart,
+    #[event(name = "transitioncancel", interface = "TransitionEvent")]
+    TransitionCancel,
+    #[event(name = "transitionend", interface = "TransitionEvent")]
+    TransitionEnd,
+    #[event(name = "transitionrun", interface = "TransitionEvent")]
+    TransitionRun,
+    #[event(name = "transitionstart", interface = "TransitionEvent")]
+    TransitionStart,
+    #[event(name = "visibilitychange", target = "document")]
+    VisibilityChange,
+    #[event(name = "wheel", interface = "WheelEvent")]
+    Wheel,
+}
+
+impl Event {
+    /// Create a custom event type.
+    pub fn custom(name: impl Into<Cow<'static, str>>) -> Self {
+        Self::Custom(name.into())
+    }
+
+    /// Return the event type with a namespace.
+    pub fn namespace(self, namespace: &str) -> EventType {
+        EventType::from(self).namespace(namespace)
+    }
+
+    /// Return the DOM event type or an error if the event cannot be
+    /// used with event listeners.
+    fn event_type(&self) -> Result<&str, Error> {
+        self.name()
+            .ok_or_else(|| Error::EventNotImplemented(self.clone()))
+    }
+}
+
+/// Event type with optional namespaces.
+///
+/// Namespaces allow to remove exactly the listeners that were added
+/// by a component, for example `"click.dropdown"` is the `click`
+/// event in the `dropdown` namespace.  The event can be omitted to
+/// match all listeners of a namespace with `off(".dropdown")`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct EventType {
+    event: Option<Event>,
+    namespaces: Vec<String>,
+}
+
+impl EventType {
+    /// Add a namespace to the event type.
+    pub fn namespace(mut self, namespace: &str) -> Self {
+        self.namespaces.push(namespace.to_string());
+        self
+    }
+
+    /// Return the event, if specified.
+    pub fn event(&self) -> Option<&Event> {
+        self.event.as_ref()
+    }
+
+    /// Return the namespaces.
+    pub fn namespaces(&self) -> &[String] {
+        &self.namespaces
+    }
+
+    /// Return the DOM event type or an error if the event is missing
+    /// or cannot be used with event listeners.
+    fn event_type(&self) -> Result<&str, Error> {
+        self.event
+            .as_ref()
+            .ok_or(Error::NoEventType)
+            .and_then(Event::event_type)
+    }
+}
+
+impl From<Event> for EventType {
+    fn from(event: Event) -> Self {
+        Self {
+            event: Some(event),
+            namespaces: vec![],
+        }
+    }
+}
+
+impl From<&str> for EventType {
+    /// Parse an event type with namespaces, e.g. `"click.dropdown"`.
+    fn from(name: &str) -> Self {
+        let mut parts = name.split('.');
+        let event = parts
+            .next()
+            .filter(|name| !name.is_empty())
+            .map(Event::from_name);
+        let namespaces = parts
+            .filter(|ns| !ns.is_empty())
+            .map(ToString::to_string)
+            .collect();
+
+        Self { event, namespaces }
+    }
+}
+
+/// Options of triggered events.
+///
+/// By default, events bubble up through the DOM and are cancelable.
+/// Events with a `detail` payload are dispatched as `CustomEvent`.
+#[derive(Clone, Debug)]
+pub struct TriggerOptions {
+    bubbles: bool,
+    cancelable: bool,
+    detail: JsValue,
+}
+
+impl Default for TriggerOptions {
+    fn default() -> Self {
+        Self {
+            bubbles: true,
+            cancelable: true,
+            detail: JsValue::UNDEFINED,
+        }
+    }
+}
+
+impl TriggerOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set if the event bubbles up through the DOM.
+    pub fn bubbles(mut self, bubbles: bool) -> Self {
+        self.bubbles = bubbles;
+        self
+    }
+
+    /// Set if the default action of the event can be prevented.
+    pub fn cancelable(mut self, cancelable: bool) -> Self {
+        self.cancelable = cancelable;
+        self
+    }
+
+    /// Set the `detail` payload of a `CustomEvent`.
+    pub fn detail(mut self, detail: impl Into<JsValue>) -> Self {
+        self.detail = detail.into();
+        self
+    }
+
+    /// Serialize the `detail` payload of a `CustomEvent`.
+    #[cfg(feature = "serde-serialize")]
+    pub fn detail_serde<T>(self, detail: &T) -> Result<Self, Error>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        let detail = serde_wasm_bindgen::to_value(detail).map_err(JsValue::from)?;
+        Ok(self.detail(detail))
+    }
+
+    /// Create a new DOM event of the event's interface.
+    ///
+    /// Interfaces without a constructor, such as `BeforeUnloadEvent`,
+    /// are created as a plain `Event`.
+    fn create(&self, event: &Event) -> Result<web_sys::Event, Error> {
+        let name = event.event_type()?;
+
+        if self.detail.is_undefined() {
+            let init = web_sys::EventInit::new();
+            init.set_bubbles(self.bubbles);
+            init.set_cancelable(self.cancelable);
+            let args = Array::of2(&name.into(), &init);
+            Reflect::get(&js_sys::global(), &event.interface().into())
+                .ok()
+                .and_then(|constructor| constructor.dyn_into::<Function>().ok())
+                .and_then(|constructor| Reflect::construct(&constructor, &args).ok())
+                .map(JsCast::unchecked_into)
+                .map_or_else(
+                    || web_sys::Event::new_with_event_init_dict(name, &init).map_err(Into::into),
+                    Ok,
+                )
+        } else {
+            let init = web_sys::CustomEventInit::new();
+            init.set_bubbles(self.bubbles);
+            init.set_cancelable(self.cancelable);
+            init.set_detail(&self.detail);
+            web_sys::CustomEvent::new_with_event_init_dict(name, &init)
+                .map(Into::into)
+                .map_err(Into::into)
+        }
+    }
+}
+
+/// Event listeners
+impl Element {
+    /// Add an event listener.
+    ///
+    /// The handler is added with `addEventListener` and does not
+    /// replace any other handlers of the same event.  It is removed
+    /// when the returned `EventListener` is dropped.
+    ///
+    /// # Nested events
+    ///
+    /// A handler is never called while it is running.  If it
+    /// dispatches an event that reaches the same handler, e.g. by
+    /// triggering it on another element of a `Collection`, the nested
+    /// call is deferred until the outer call returned.  The deferred
+    /// call runs after the nested dispatch finished: `trigger` returns
+    /// before the handler was called, `prevent_default` and
+    /// `stop_propagation` have no effect and the `current_target` of
+    /// the event is not set anymore.
+    pub fn on<E, F>(&self, event: E, handler: F) -> Result<EventListener, Error>
+    where
+        E: Into<EventType>,
+        F: FnMut(web_sys::Event) + 'static,
+    {
+        add_handler(
+            self.0.as_ref(),
+            event.into(),
+            &Default::default(),
+            false,
+            handler,
+        )
+    }
+
+    /// Add an event listener with options, e.g. passive or capture.
+    ///
+    /// Nested calls of the handler are deferred, see `Element::on`.
+    pub fn on_with<E, F>(
+        &self,
+        event: E,
+        options: &ListenerOptions,
+        handler: F,
+    ) -> Result<EventListener, Error>
+    where
+        E: Into<EventType>,
+        F: FnMut(web_sys::Event) + 'static,
+    {
+        add_handler(self.0.as_ref(), event.into(), options, false, handler)
+    }
+
+    /// Remove all event listeners of the event type and namespaces
+    /// that were added by this crate.
+    pub fn off<E>(&self, event: E) -> Result<(), Error>
+    where
+        E: Into<EventType>,
+    {
+        remove(self.0.as_ref(), &event.into())
+    }
+
+    /// Add an event listener that is called at most once.
+    ///
+    /// The listener removes itself after the first call.
+    ///
+    /// Nested calls of the handler are deferred, see `Element::on`.
+    pub fn one<E, F>(&self, event: E, handler: F) -> Result<EventListener, Error>
+    where
+        E: Into<EventType>,
+        F: FnMut(web_sys::Event) + 'static,
+    {
+        add_handler(
+            self.0.as_ref(),
+            event.into(),
+            &Default::default(),
+            true,
+            handler,
+        )
+    }
+
+    /// Add a pair of `mouseenter` and `mouseleave` event listeners.
+    pub fn hover<F, G>(&self, enter: F, leave: G) -> Result<EventListener, Error>
+    where
+        F: FnMut(web_sys::Event) + 'static,
+        G: FnMut(web_sys::Event) + 'static,
+    {
+        let mut listener = self.on(Event::MouseEnter, enter)?;
+        listener.append(self.on(Event::MouseLeave, leave)?);
+        Ok(listener)
+    }
+
+    /// Add a delegated event listener for descendants.
+    ///
+    /// The listener is added once to this element and calls the
+    /// handler for events of descendants that match the selectors,
+    /// including elements that are inserted later.  The matching
+    /// element is the current target of the handler's `EventContext`.
+    ///
+    /// Nested calls of the handler are deferred, see `Element::on`.
+    pub fn delegate<E, F>(
+        &self,
+        event: E,
+        selectors: &str,
+        handler: F,
+    ) -> Result<EventListener, Error>
+    where
+        E: Into<EventType>,
+        F: FnMut(EventContext) + 'static,
+    {
+        self.is(selectors)?;
+        add(
+            self.0.as_ref(),
+            event.into(),
+            &Default::default(),
+            delegate(selectors, handler),
+            None,
+        )
+    }
+}
+
+/// Triggering events
+impl Element {
+    /// Dispatch the event on the element.
+    ///
+    /// Returns `false` if the event is cancelable and a handler
+    /// prevented the default action.
+    ///
+    /// Handlers that are already running when the event reaches them
+    /// are called after the dispatch, so they cannot change the
+    /// result, see `Element::on`.
+    pub fn trigger(&self, event: Event) -> Result<bool, Error> {
+        self.trigger_with(event, &TriggerOptions::default())
+    }
+
+    /// Dispatch the event with the specified options.
+    pub fn trigger_with(&self, event: Event, options: &TriggerOptions) -> Result<bool, Error> {
+        dispatch(self.0.as_ref(), &event, options)
+    }
+
+    /// Dispatch a `CustomEvent` with the `detail` payload.
+    pub fn trigger_custom(&self, name: &str, detail: impl Into<JsValue>) -> Result<bool, Error> {
+        self.trigger_with(
+            Event::from_name(name),
+            &TriggerOptions::new().detail(detail),
+        )
+    }
+
+    /// Call the handlers that were added by this crate.
+    ///
+    /// Unlike `trigger`, the event is not dispatched: it does not
+    /// bubble and does not trigger the default action.
+    pub fn trigger_handler(&self, event: Event) -> Result<(), Error> {
+        call_handlers(self.0.as_ref(), &event)
+    }
+}
+
+/// Event listeners
+impl Collection {
+    /// Add an event listener to every element in the collection.
+    ///
+    /// The handler is shared by all elements and the returned
+    /// `EventListener` owns the listeners of all elements.
+    ///
+    /// Nested calls of the handler are deferred, see `Element::on`.
+    pub fn on<E, F>(&self, event: E, handler: F) -> Result<EventListener, Error>
+    where
+        E: Into<EventType>,
+        F: FnMut(web_sys::Event) + 'static,
+    {
+        self.add_listeners(
+            event.into(),
+            &Default::default(),
+            listener::callback(handler),
+            || None,
+        )
+    }
+
+    /// Add an event listener with options to every element in the
+    /// collection.
+    ///
+    /// Nested calls of the handler are deferred, see `Element::on`.
+    pub fn on_with<E, F>(
+        &self,
+        event: E,
+        options: &ListenerOptions,
+        handler: F,
+    ) -> Result<EventListener, Error>
+    where
+        E: Into<EventType>,
+        F: FnMut(web_sys::Event) + 'static,
+    {
+        self.add_listeners(event.into(), options, listener::callback(handler), || None)
+    }
+
+    /// Remove all event listeners of the event type and namespaces
+    /// from every element in the collection.
+    pub fn off<E>(&self, event: E) -> Result<(), Error>
+    where
+        E: Into<EventType>,
+    {
+        let event = event.into();
+        for element in self.0.iter() {
+            remove(element.0.as_ref(), &event)?;
+        }
+
+        Ok(())
+    }
+
+    /// Add an event listener to every element in the collection
+    /// that is called at most once per element.
+    ///
+    /// Nested calls of the handler are deferred, see `Element::on`.
+    pub fn one<E, F>(&self, event: E, handler: F) -> Result<EventListener, Error>
+    where
+        E: Into<EventType>,
+        F: FnMut(web_sys::Event) + 'static,
+    {
+        self.add_listeners(
+            event.into(),
+            &Default::default(),
+            listener::callback(handler),
+            || Some(Default::default()),
+        )
+    }
+
+    /// Add an event listener to every element in the collection
+    /// that is called at most once for the whole collection.
+    ///
+    /// The listeners of all elements are removed after the first
+    /// call on any of the elements.
+    ///
+    /// Nested calls of the handler are deferred, see `Element::on`.
+    pub fn one_for_all<E, F>(&self, event: E, handler: F) -> Result<EventListener, Error>
+    where
+        E: Into<EventType>,
+        F: FnMut(web_sys::Event) + 'static,
+    {
+        let once = listener::Once::default();
+        self.add_listeners(
+            event.into(),
+            &Default::default(),
+            listener::callback(handler),
+            || Some(once.clone()),
+        )
+    }
+
+    /// Add a pair of `mouseenter` and `mouseleave` event listeners
+    /// to every element in the collection.
+    pub fn hover<F, G>(&self, enter: F, leave: G) -> Result<EventListener, Error>
+    where
+        F: FnMut(web_sys::Event) + 'static,
+        G: FnMut(web_sys::Event) + 'static,
+    {
+        let mut listener = self.on(Event::MouseEnter, enter)?;
+        listener.append(self.on(Event::MouseLeave, leave)?);
+        Ok(listener)
+    }
+
+    /// Add a delegated event listener to every element in the
+    /// collection.
+    ///
+    /// Nested calls of the handler are deferred, see `Element::on`.
+    pub fn delegate<E, F>(
+        &self,
+        event: E,
+        selectors: &str,
+        handler: F,
+    ) -> Result<EventListener, Error>
+    where
+        E: Into<EventType>,
+        F: FnMut(EventContext) + 'static,
+    {
+        for element in self.0.iter() {
+            element.is(selectors)?;
+        }
+        self.add_listeners(
+            event.into(),
+            &Default::default(),
+            delegate(selectors, handler),
+            || None,
+        )
+    }
+}
+
+/// Triggering events
+impl Collection {
+    /// Dispatch the event on every element in the collection.
+    ///
+    /// Handlers that are already running are called after the
+    /// dispatch, see `Element::on`.
+    pub fn trigger(&self, event: Event) -> Result<(), Error> {
+        self.trigger_with(event, &TriggerOptions::default())
+    }
+
+    /// Dispatch the event with the specified options on every
+    /// element in the collection.
+    pub fn trigger_with(&self, event: Event, options: &TriggerOptions) -> Result<(), Error> {
+        for element in self.0.iter() {
+            element.trigger_with(event.clone(), options)?;
+        }
+
+        Ok(())
+    }
+
+    /// Dispatch a `CustomEvent` on every element in the collection.
+    pub fn trigger_custom(&self, name: &str, detail: impl Into<JsValue>) -> Result<(), Error> {
+        self.trigger_with(
+            Event::from_name(name),
+            &TriggerOptions::new().detail(detail),
+        )
+    }
+
+    /// Call the handlers that were added by this crate on every
+    /// element in the collection.
+    pub fn trigger_handler(&self, event: Event) -> Result<(), Error> {
+        for element in self.0.iter() {
+            element.trigger_handler(event.clone())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Collection {
+    /// Add the shared callback to every element in the collection.
+    fn add_listeners<O>(
+        &self,
+        event: EventType,
+        options: &ListenerOptions,
+        callback: listener::Callback,
+        once: O,
+    ) -> Result<EventListener, Error>
+    where
+        O: Fn() -> Option<listener::Once>,
+    {
+        let mut listener = EventListener::default();
+        for element in self.0.iter() {
+            listener.append(add(
+                element.0.as_ref(),
+                event.clone(),
+                options,
+                callback.clone(),
+                once(),
+            )?);
+        }
+
+        Ok(listener)
+    }
+}
+
+/// Event listeners
+impl Document {
+    /// Add an event listener to the document.
+    ///
+    /// Nested calls of the handler are deferred, see `Element::on`.
+    pub fn on<E, F>(&self, event: E, handler: F) -> Result<EventListener, Error>
+    where
+        E: Into<EventType>,
+        F: FnMut(web_sys::Event) + 'static,
+    {
+        self.on_with(event, &Default::default(), handler)
+    }
+
+    /// Add an event listener with options to the document.
+    ///
+    /// Nested calls of the handler are deferred, see `Element::on`.
+    pub fn on_with<E, F>(
+        &self,
+        event: E,
+        options: &ListenerOptions,
+        handler: F,
+    ) -> Result<EventListener, Error>
+    where
+        E: Into<EventType>,
+        F: FnMut(web_sys::Event) + 'static,
+    {
+        add_handler(self.0.as_ref(), event.into(), options, false, handler)
+    }
+
+    /// Remove event listeners from the document, see `Element::off`.
+    pub fn off<E>(&self, event: E) -> Result<(), Error>
+    where
+        E: Into<EventType>,
+    {
+        remove(self.0.as_ref(), &event.into())
+    }
+
+    /// Call the handler when the DOM is ready.
+    ///
+    /// The handler is called immediately if the document is already
+    /// loaded, otherwise it is called once on `DOMContentLoaded`.
+    pub fn ready<F>(&self, handler: F) -> Result<EventListener, Error>
+    where
+        F: FnOnce() + 'static,
+    {
+        if self.0.ready_state() != "loading" {
+            handler();
+            return Ok(EventListener::default());
+        }
+
+        let mut handler = Some(handler);
+        add_handler(
+            self.0.as_ref(),
+            Event::DomContentLoaded.into(),
+            &Default::default(),
+            true,
+            move |_| {
+                if let Some(handler) = handler.take() {
+                    handler()
+                }
+            },
+        )
+    }
+
+    /// Add an event listener to the document that is called at most
+    /// once.
+    ///
+    /// Nested calls of the handler are deferred, see `Element::on`.
+    pub fn one<E, F>(&self, event: E, handler: F) -> Result<EventListener, Error>
+    where
+        E: Into<EventType>,
+        F: FnMut(web_sys::Event) + 'static,
+    {
+        add_handler(
+            self.0.as_ref(),
+            event.into(),
+            &Default::default(),
+            true,
+            handler,
+        )
+    }
+
+    /// Add a delegated event listener for all elements in the
+    /// document.
+    ///
+    /// Elements that are inserted into the document later are
+    /// handled as well.
+    ///
+    /// Nested calls of the handler are deferred, see `Element::on`.
+    pub fn delegate<E, F>(
+        &self,
+        event: E,
+        selectors: &str,
+        handler: F,
+    ) -> Result<EventListener, Error>
+    where
+        E: Into<EventType>,
+        F: FnMut(EventContext) + 'static,
+    {
+        self.0.query_selector(selectors)?;
+        add(
+            self.0.as_ref(),
+            event.into(),
+            &Default::default(),
+            delegate(selectors, handler),
+            None,
+        )
+    }
+}
+
+/// Triggering events
+impl Document {
+    /// Dispatch the event on the document.
+    ///
+    /// Handlers that are already running are called after the
+    /// dispatch, see `Element::on`.
+    pub fn trigger(&self, event: Event) -> Result<bool, Error> {
+        self.trigger_with(event, &TriggerOptions::default())
+    }
+
+    /// Dispatch the event with the specified options on the
+    /// document.
+    pub fn trigger_with(&self, event: Event, options: &TriggerOptions) -> Result<bool, Error> {
+        dispatch(self.0.as_ref(), &event, options)
+    }
+
+    /// Dispatch a `CustomEvent` with the `detail` payload on the
+    /// document.
+    pub fn trigger_custom(&self, name: &str, detail: impl Into<JsValue>) -> Result<bool, Error> {
+        self.trigger_with(
+            Event::from_name(name),
+            &TriggerOptions::new().detail(detail),
+        )
+    }
+
+    /// Call the handlers of the document that were added by this
+    /// crate.
+    pub fn trigger_handler(&self, event: Event) -> Result<(), Error> {
+        call_handlers(self.0.as_ref(), &event)
+    }
+}
+
+/// Event listeners
+impl Window {
+    /// Add an event listener to the window.
+    ///
+    /// Nested calls of the handler are deferred, see `Element::on`.
+    pub fn on<E, F>(&self, event: E, handler: F) -> Result<EventListener, Error>
+    where
+        E: Into<EventType>,
+        F: FnMut(web_sys::Event) + 'static,
+    {
+        self.on_with(event, &Default::default(), handler)
+    }
+
+    /// Add an event listener with options to the window.
+    ///
+    /// Nested calls of the handler are deferred, see `Element::on`.
+    pub fn on_with<E, F>(
+        &self,
+        event: E,
+        options: &ListenerOptions,
+        handler: F,
+    ) -> Result<EventListener, Error>
+    where
+        E: Into<EventType>,
+        F: FnMut(web_sys::Event) + 'static,
+    {
+        add_handler(self.0.as_ref(), event.into(), options, false, handler)
+    }
+
+    /// Remove event listeners from the window, see `Element::off`.
+    pub fn off<E>(&self, event: E) -> Result<(), Error>
+    where
+        E: Into<EventType>,
+    {
+        remove(self.0.as_ref(), &event.into())
+    }
+
+    /// Add an event listener to the window that is called at most
+    /// once.
+    ///
+    /// Nested calls of the handler are deferred, see `Element::on`.
+    pub fn one<E, F>(&self, event: E, handler: F) -> Result<EventListener, Error>
+    where
+        E: Into<EventType>,
+        F: FnMut(web_sys::Event) + 'static,
+    {
+        add_handler(
+            self.0.as_ref(),
+            event.into(),
+            &Default::default(),
+            true,
+            handler,
+        )
+    }
+}
+
+/// Triggering events
+impl Window {
+    /// Dispatch the event on the window.
+    ///
+    /// Handlers that are already running are called after the
+    /// dispatch, see `Element::on`.
+    pub fn trigger(&self, event: Event) -> Result<bool, Error> {
+        self.trigger_with(event, &TriggerOptions::default())
+    }
+
+    /// Dispatch the event with the specified options on the window.
+    pub fn trigger_with(&self, event: Event, options: &TriggerOptions) -> Result<bool, Error> {
+        dispatch(self.0.as_ref(), &event, options)
+    }
+
+    /// Dispatch a `CustomEvent` with the `detail` payload on the
+    /// window.
+    pub fn trigger_custom(&self, name: &str, detail: impl Into<JsValue>) -> Result<bool, Error> {
+        self.trigger_with(
+            Event::from_name(name),
+            &TriggerOptions::new().detail(detail),
+        )
+    }
+
+    /// Call the handlers of the window that were added by this crate.
+    pub fn trigger_handler(&self, event: Event) -> Result<(), Error> {
+        call_handlers(self.0.as_ref(), &event)
+    }
+}
+
+/// Add a listener for the event type to the target.
+fn add(
+    target: &EventTarget,
+    event: EventType,
+    options: &ListenerOptions,
+    callback: listener::Callback,
+    once: Option<listener::Once>,
+) -> Result<EventListener, Error> {
+    listener::add(
+        target,
+        event.event_type()?,
+        event.namespaces(),
+        options,
+        callback,
+        once,
+    )
+}
+
+/// Add an event handler to the target.
+///
+/// Handlers that are called at most once remove themselves after
+/// the first call.
+fn add_handler<F>(
+    target: &EventTarget,
+    event: EventType,
+    options: &ListenerOptions,
+    once: bool,
+    handler: F,
+) -> Result<EventListener, Error>
+where
+    F: FnMut(web_sys::Event) + 'static,
+{
+    let once = if once { Some(Default::default()) } else { None };
+    add(target, event, options, listener::callback(handler), once)
+}
+
+/// Remove all listeners of the event type from the target.
+fn remove(target: &EventTarget, event: &EventType) -> Result<(), Error> {
+    let name = event.event.as_ref().map(Event::event_type).transpose()?;
+    listener::remove(target, name, event.namespaces());
+    Ok(())
+}
+
+/// Dispatch a new event on the target.
+fn dispatch(target: &EventTarget, event: &Event, options: &TriggerOptions) -> Result<bool, Error> {
+    target
+        .dispatch_event(&options.create(event)?)
+        .map_err(Into::into)
+}
+
+/// Call the listeners of the target without dispatching the event.
+fn call_handlers(target: &EventTarget, event: &Event) -> Result<(), Error> {
+    let name = event.event_type()?;
+    let dom_event = TriggerOptions::new().bubbles(false).create(event)?;
+    listener::call(target, name, &dom_event);
+    Ok(())
+}
+
+/// Create the callback of a delegated event listener.
+///
+/// The current target of the event is the element or document that
+/// the listener is added to; only its descendants are matched.  The
+/// element is matched before the handler is queued, as the current
+/// target is not available after the dispatch.
+fn delegate<F>(selectors: &str, handler: F) -> listener::Callback
+where
+    F: FnMut(EventContext) + 'static,
+{
+    let selectors = selectors.to_string();
+    let handler = listener::queued(handler);
+    Rc::new(move |event: web_sys::Event| {
+        let root = match event
+            .current_target()
+            .and_then(|target| target.dyn_into::<web_sys::Node>().ok())
+        {
+            Some(root) => root,
+            None => return,
+        };
+        let matched = Element::try_from(event.clone())
+            .and_then(|target| target.closest(&selectors))
+            .ok()
+            .flatten()
+            .filter(|element| {
+                let node: &web_sys::Node = element.0.as_ref();
+                node != &root && root.contains(Some(node))
+            });
+        if let Some(element) = matched {
+            handler(EventContext::with_current_target(event, element))
+        }
+    })
 }
@@ -0,0 +1,444 @@
+//! Event listener registry
+
+use crate::error::Error;
+use js_sys::{FinalizationRegistry, WeakMap, WeakRef};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, HashMap, VecDeque},
+    mem,
+    rc::Rc,
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{AbortSignal, AddEventListenerOptions, EventTarget};
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = RefCell::new(Registry::default());
+    static TARGETS: Targets = Targets::default();
+}
+
+/// Shared callback that is called by the JS closure of a listener.
+pub(crate) type Callback = Rc<dyn Fn(web_sys::Event)>;
+
+/// Wrap an event handler into a shared callback.
+///
+/// The same callback can be added to multiple targets, for example
+/// all elements of a `Collection`.  Nested calls of the handler,
+/// e.g. by triggering the event on another element of the collection
+/// from within the handler, are queued and called after the outer
+/// call returned.
+pub(crate) fn callback<F>(handler: F) -> Callback
+where
+    F: FnMut(web_sys::Event) + 'static,
+{
+    Rc::new(queued(handler))
+}
+
+/// Call the handler with one event at a time.
+///
+/// A queued event is handled after its dispatch finished, so the
+/// `current_target` of the DOM event is no longer set.
+pub(crate) fn queued<T, F>(handler: F) -> impl Fn(T)
+where
+    F: FnMut(T),
+{
+    let handler = RefCell::new(handler);
+    let queue = RefCell::new(VecDeque::new());
+    move |event| {
+        queue.borrow_mut().push_back(event);
+        while let Ok(mut handler) = handler.try_borrow_mut() {
+            let event = queue.borrow_mut().pop_front();
+            match event {
+                Some(event) => (*handler)(event),
+                None => break,
+            }
+        }
+    }
+}
+
+/// JS closure that is added as the DOM event listener.
+type JsClosure = Closure<dyn FnMut(web_sys::Event)>;
+
+/// Group of listeners that are removed after one of them was called.
+#[derive(Clone, Default)]
+pub(crate) struct Once(Rc<RefCell<Vec<usize>>>);
+
+/// Options of event listeners.
+///
+/// The options are mapped to the `AddEventListenerOptions` of the
+/// DOM, except for `once` and `signal` that also remove the listener
+/// from this crate's registry.
+#[derive(Clone, Debug, Default)]
+pub struct ListenerOptions {
+    capture: bool,
+    passive: Option<bool>,
+    once: bool,
+    signal: Option<AbortSignal>,
+}
+
+impl ListenerOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Call the listener in the capture phase.
+    pub fn capture(mut self, capture: bool) -> Self {
+        self.capture = capture;
+        self
+    }
+
+    /// Indicate that the listener never calls `prevent_default`.
+    ///
+    /// If not set, the browser's default is used, which is passive
+    /// for some touch and wheel events.
+    pub fn passive(mut self, passive: bool) -> Self {
+        self.passive = Some(passive);
+        self
+    }
+
+    /// Remove the listener after it was called once.
+    pub fn once(mut self, once: bool) -> Self {
+        self.once = once;
+        self
+    }
+
+    /// Remove the listener when the signal is aborted.
+    pub fn signal(mut self, signal: &AbortSignal) -> Self {
+        self.signal = Some(signal.clone());
+        self
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    next_id: usize,
+    listeners: BTreeMap<usize, Listener>,
+    /// Listener ids by the key of their target.
+    targets: HashMap<u32, Vec<usize>>,
+}
+
+impl Registry {
+    /// Return the listeners of the target with the key.
+    fn of_target(&self, key: Option<u32>) -> impl Iterator<Item = (&usize, &Listener)> + '_ {
+        key.and_then(|key| self.targets.get(&key))
+            .into_iter()
+            .flatten()
+            .filter_map(move |id| self.listeners.get_key_value(id))
+    }
+}
+
+/// Keys of the event targets in the registry.
+///
+/// The keys are stored in a `WeakMap`, so the listeners of a target
+/// are found without comparing it with every listener and without
+/// the key keeping the target alive.  A handler that captures its
+/// element still keeps it alive until the listener is removed with
+/// `off`, `remove` or by dropping the `EventListener`.  Otherwise the
+/// listeners of a target that was garbage collected, e.g. after it
+/// was replaced with `set_html`, are removed by the finalizer.
+struct Targets {
+    keys: WeakMap,
+    next_key: Cell<u32>,
+    finalizer: FinalizationRegistry,
+    _cleanup: Closure<dyn FnMut(JsValue)>,
+}
+
+impl Default for Targets {
+    fn default() -> Self {
+        let cleanup = Closure::wrap(Box::new(|key: JsValue| {
+            if let Some(key) = key.as_f64() {
+                let ids = REGISTRY.with(|registry| {
+                    registry
+                        .borrow()
+                        .targets
+                        .get(&(key as u32))
+                        .cloned()
+                        .unwrap_or_default()
+                });
+                remove_ids(&ids);
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+
+        Self {
+            keys: WeakMap::new(),
+            next_key: Cell::new(0),
+            finalizer: FinalizationRegistry::new(cleanup.as_ref().unchecked_ref()),
+            _cleanup: cleanup,
+        }
+    }
+}
+
+impl Targets {
+    /// Return the key of the target, if it has one.
+    fn get(&self, target: &EventTarget) -> Option<u32> {
+        self.keys
+            .get(target.unchecked_ref())
+            .as_f64()
+            .map(|key| key as u32)
+    }
+
+    /// Return the key of the target or assign a new one.
+    fn get_or_insert(&self, target: &EventTarget) -> u32 {
+        self.get(target).unwrap_or_else(|| {
+            let key = self.next_key.get() + 1;
+            self.next_key.set(key);
+            self.keys.set(target.unchecked_ref(), &key.into());
+            self.finalizer.register(target, &key.into());
+            key
+        })
+    }
+}
+
+/// Return the key of the target, if it has one.
+fn key(target: &EventTarget) -> Option<u32> {
+    TARGETS.with(|targets| targets.get(target))
+}
+
+/// Event listener that is added to the target as long as it exists.
+struct Listener {
+    /// The target is not kept alive by the registry.
+    target: WeakRef<EventTarget>,
+    key: u32,
+    name: String,
+    namespaces: Vec<String>,
+    options: ListenerOptions,
+    /// The handler without the `Once` wrapper.
+    handler: Callback,
+    once: bool,
+    callback: Callback,
+    closure: JsClosure,
+    abort: Option<(AbortSignal, JsClosure)>,
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        if let Some(target) = self.target.deref() {
+            target
+                .remove_event_listener_with_callback_and_bool(
+                    &self.name,
+                    self.closure.as_ref().unchecked_ref(),
+                    self.options.capture,
+                )
+                .ok();
+        }
+        if let Some((signal, closure)) = &self.abort {
+            signal
+                .remove_event_listener_with_callback("abort", closure.as_ref().unchecked_ref())
+                .ok();
+        }
+    }
+}
+
+/// Add a new listener to the target and the registry.
+///
+/// If the listener is part of a `Once` group, all listeners of the
+/// group are removed after the first call.
+pub(crate) fn add(
+    target: &EventTarget,
+    name: &str,
+    namespaces: &[String],
+    options: &ListenerOptions,
+    callback: Callback,
+    once: Option<Once>,
+) -> Result<EventListener, Error> {
+    if let Some(signal) = &options.signal {
+        if signal.aborted() {
+            return Ok(EventListener::default());
+        }
+    }
+
+    let id = REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        registry.next_id += 1;
+        registry.next_id
+    });
+
+    let once = once.or_else(|| {
+        if options.once {
+            Some(Once::default())
+        } else {
+            None
+        }
+    });
+    if let Some(once) = &once {
+        once.0.borrow_mut().push(id);
+    }
+    let handler = callback.clone();
+    let is_once = once.is_some();
+    let callback: Callback = Rc::new(move |event| {
+        if let Some(once) = &once {
+            // Remove the group before calling the handler, so events
+            // that it triggers cannot call it again.  The group is
+            // empty if another listener of it was already called.
+            let ids = once.0.take();
+            if ids.is_empty() {
+                return;
+            }
+            remove_ids(&ids);
+        }
+        callback(event);
+    });
+    let closure = {
+        let callback = callback.clone();
+        Closure::wrap(Box::new(move |event| callback(event)) as Box<dyn FnMut(web_sys::Event)>)
+    };
+    let init = AddEventListenerOptions::new();
+    init.set_capture(options.capture);
+    init.set_once(options.once);
+    if let Some(passive) = options.passive {
+        init.set_passive(passive);
+    }
+    target.add_event_listener_with_callback_and_add_event_listener_options(
+        name,
+        closure.as_ref().unchecked_ref(),
+        &init,
+    )?;
+
+    let abort = match &options.signal {
+        Some(signal) => {
+            let closure = Closure::wrap(
+                Box::new(move |_| remove_ids(&[id])) as Box<dyn FnMut(web_sys::Event)>
+            );
+            signal.add_event_listener_with_callback("abort", closure.as_ref().unchecked_ref())?;
+            Some((signal.clone(), closure))
+        }
+        None => None,
+    };
+
+    let key = TARGETS.with(|targets| targets.get_or_insert(target));
+    let listener = Listener {
+        target: WeakRef::new(target),
+        key,
+        name: name.to_string(),
+        namespaces: namespaces.to_vec(),
+        options: options.clone(),
+        handler,
+        once: is_once,
+        callback,
+        closure,
+        abort,
+    };
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        registry.listeners.insert(id, listener);
+        registry.targets.entry(key).or_default().push(id);
+    });
+
+    Ok(EventListener(vec![id]))
+}
+
+/// Call all listeners of the event type on the target directly.
+pub(crate) fn call(target: &EventTarget, name: &str, event: &web_sys::Event) {
+    let callbacks = REGISTRY.with(|registry| {
+        registry
+            .borrow()
+            .of_target(key(target))
+            .filter(|(_, listener)| listener.name == name)
+            .map(|(_, listener)| listener.callback.clone())
+            .collect::<Vec<_>>()
+    });
+    for callback in callbacks {
+        callback(event.clone());
+    }
+}
+
+/// Add copies of all listeners of a target to another target.
+///
+/// The copied listeners are not owned by an `EventListener` handle;
+/// they can be removed with `off`.  Listeners that are called at most
+/// once are copied into a new group of the copy.
+pub(crate) fn copy(from: &EventTarget, to: &EventTarget) -> Result<(), Error> {
+    let listeners = REGISTRY.with(|registry| {
+        registry
+            .borrow()
+            .of_target(key(from))
+            .map(|(_, listener)| {
+                (
+                    listener.name.clone(),
+                    listener.namespaces.clone(),
+                    listener.options.clone(),
+                    listener.handler.clone(),
+                    listener.once,
+                )
+            })
+            .collect::<Vec<_>>()
+    });
+    for (name, namespaces, options, handler, once) in listeners {
+        let once = if once { Some(Once::default()) } else { None };
+        add(to, &name, &namespaces, &options, handler, once)?.forget();
+    }
+    Ok(())
+}
+
+/// Remove matching listeners from the target.
+///
+/// Listeners match if they have the event type, or any type if it
+/// is `None`, and all of the namespaces.
+pub(crate) fn remove(target: &EventTarget, name: Option<&str>, namespaces: &[String]) {
+    let ids = REGISTRY.with(|registry| {
+        registry
+            .borrow()
+            .of_target(key(target))
+            .filter(|(_, listener)| {
+                name.map(|name| listener.name == name).unwrap_or(true)
+                    && namespaces.iter().all(|ns| listener.namespaces.contains(ns))
+            })
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>()
+    });
+    remove_ids(&ids);
+}
+
+fn remove_ids(ids: &[usize]) {
+    // The listeners are dropped after releasing the registry as
+    // dropping a handler might recursively access the registry.
+    let _listeners = REGISTRY.with(|registry| {
+        let registry = &mut *registry.borrow_mut();
+        ids.iter()
+            .filter_map(|id| {
+                let listener = registry.listeners.remove(id)?;
+                if let Some(ids) = registry.targets.get_mut(&listener.key) {
+                    ids.retain(|other| other != id);
+                    if ids.is_empty() {
+                        registry.targets.remove(&listener.key);
+                    }
+                }
+                Some(listener)
+            })
+            .collect::<Vec<_>>()
+    });
+}
+
+/// Handle of one or more event listeners that were added with `on`.
+///
+/// Unlike the `set_on` event handler properties, multiple listeners
+/// can be added to the same element and event type.  The handle owns
+/// the listeners and removes them when it is dropped, call `forget`
+/// to keep them for the lifetime of the page.
+#[derive(Debug, Default)]
+#[must_use = "the event listener is removed when it is dropped"]
+pub struct EventListener(Vec<usize>);
+
+impl EventListener {
+    /// Move all listeners of another handle into this handle.
+    pub fn append(&mut self, mut other: Self) {
+        self.0.append(&mut other.0);
+    }
+
+    /// Keep the listeners without removing them on drop.
+    ///
+    /// The listeners can still be removed with `off`.
+    pub fn forget(mut self) {
+        self.0.clear();
+    }
+
+    /// Remove all event listeners of this handle.
+    pub fn off(self) {
+        drop(self)
+    }
+}
+
+impl Drop for EventListener {
+    fn drop(&mut self) {
+        remove_ids(&mem::take(&mut self.0));
+    }
+}
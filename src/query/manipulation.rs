@@ -2,8 +2,221 @@
 
 use crate::{
     error::Error,
-    query::{Collection, Element},
+    query::{data, listener, Collection, Document, Element},
 };
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Content that can be inserted into the DOM.
+///
+/// Strings are parsed as HTML, use `Content::Text` to insert text.
+#[derive(Clone, Debug)]
+pub enum Content {
+    Element(Element),
+    Collection(Collection),
+    Html(String),
+    Text(String),
+}
+
+impl Content {
+    pub fn html(html: &str) -> Self {
+        Self::Html(html.to_string())
+    }
+
+    pub fn text(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<Element> for Content {
+    fn from(element: Element) -> Self {
+        Self::Element(element)
+    }
+}
+
+impl From<&Element> for Content {
+    fn from(element: &Element) -> Self {
+        Self::Element(element.clone())
+    }
+}
+
+impl From<Collection> for Content {
+    fn from(collection: Collection) -> Self {
+        Self::Collection(collection)
+    }
+}
+
+impl From<&Collection> for Content {
+    fn from(collection: &Collection) -> Self {
+        Self::Collection(collection.clone())
+    }
+}
+
+impl From<&str> for Content {
+    fn from(html: &str) -> Self {
+        Self::html(html)
+    }
+}
+
+impl From<String> for Content {
+    fn from(html: String) -> Self {
+        Self::Html(html)
+    }
+}
+
+/// Target of the reverse insertion methods, such as `append_to`.
+#[derive(Clone, Debug)]
+pub enum Target<'a> {
+    /// Selectors that are matched in the element's document.
+    Selector(&'a str),
+    Collection(Collection),
+}
+
+impl<'a> Target<'a> {
+    /// Resolve the target elements in the document of the element.
+    fn resolve(self, element: Option<&Element>) -> Result<Collection, Error> {
+        match self {
+            Target::Selector(selectors) => match element.and_then(|elem| elem.owner_document()) {
+                Some(document) => document
+                    .query_selector_all(selectors)
+                    .map(Into::into)
+                    .map_err(Into::into),
+                None => Ok(Collection::new()),
+            },
+            Target::Collection(collection) => Ok(collection),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Target<'a> {
+    fn from(selectors: &'a str) -> Self {
+        Self::Selector(selectors)
+    }
+}
+
+impl From<Collection> for Target<'_> {
+    fn from(collection: Collection) -> Self {
+        Self::Collection(collection)
+    }
+}
+
+impl From<&Collection> for Target<'_> {
+    fn from(collection: &Collection) -> Self {
+        Self::Collection(collection.clone())
+    }
+}
+
+impl From<Element> for Target<'_> {
+    fn from(element: Element) -> Self {
+        Self::Collection(element.into())
+    }
+}
+
+impl From<&Element> for Target<'_> {
+    fn from(element: &Element) -> Self {
+        Self::Collection(element.clone().into())
+    }
+}
+
+/// Insertion position relative to the target element.
+#[derive(Clone, Copy)]
+enum Position {
+    BeforeBegin,
+    AfterBegin,
+    BeforeEnd,
+    AfterEnd,
+}
+
+impl Position {
+    fn as_str(self) -> &'static str {
+        match self {
+            Position::BeforeBegin => "beforebegin",
+            Position::AfterBegin => "afterbegin",
+            Position::BeforeEnd => "beforeend",
+            Position::AfterEnd => "afterend",
+        }
+    }
+}
+
+/// Insert the content at the position of the target.
+///
+/// Elements are moved, or deep cloned if `clone` is set.
+fn insert(
+    target: &Element,
+    position: Position,
+    content: &Content,
+    clone: bool,
+) -> Result<(), Error> {
+    let nodes = match content {
+        Content::Element(element) => vec![element.clone()],
+        Content::Collection(collection) => collection.0.iter().cloned().collect(),
+        Content::Html(html) => {
+            return target
+                .insert_adjacent_html(position.as_str(), html)
+                .map_err(Into::into)
+        }
+        Content::Text(text) => {
+            return target
+                .insert_adjacent_text(position.as_str(), text)
+                .map_err(Into::into)
+        }
+    };
+
+    let array = js_sys::Array::new();
+    for element in nodes {
+        let node: JsValue = if clone {
+            element.clone_node_with_deep(true)?.into()
+        } else {
+            element.0.into()
+        };
+        array.push(&node);
+    }
+
+    match position {
+        Position::BeforeBegin => target.before_with_node(&array),
+        Position::AfterBegin => target.prepend_with_node(&array),
+        Position::BeforeEnd => target.append_with_node(&array),
+        Position::AfterEnd => target.after_with_node(&array),
+    }
+    .map_err(Into::into)
+}
+
+/// Insert the content into every target, elements are cloned for all
+/// targets but the last one.
+fn insert_all<'a, I>(targets: I, position: Position, content: &Content) -> Result<(), Error>
+where
+    I: ExactSizeIterator<Item = &'a Element>,
+{
+    let last = targets.len().saturating_sub(1);
+    for (i, target) in targets.enumerate() {
+        insert(target, position, content, i < last)?;
+    }
+    Ok(())
+}
+
+/// DOM manipulation
+impl Document {
+    /// Create elements from an HTML fragment.
+    ///
+    /// The HTML is parsed with a `<template>` element, so scripts are
+    /// not executed and elements such as `<tr>` can be created
+    /// without their parents.  Text nodes at the top level are
+    /// ignored.  The elements are imported from the template's inert
+    /// document into this document.
+    pub fn create(&self, html: &str) -> Result<Collection, Error> {
+        let template = self
+            .0
+            .create_element("template")?
+            .dyn_into::<web_sys::HtmlTemplateElement>()
+            .map_err(|_| Error::DynRefFailed)?;
+        template.set_inner_html(html);
+        let fragment = self
+            .0
+            .import_node_with_deep(&template.content(), true)?
+            .dyn_into::<web_sys::DocumentFragment>()
+            .map_err(|_| Error::DynRefFailed)?;
+        Ok(fragment.children().into())
+    }
+}
 
 /// DOM manipulation
 impl Element {
@@ -17,6 +230,146 @@ impl Element {
         self.dyn_ref::<web_sys::HtmlElement>()?.set_inner_text(text);
         Ok(())
     }
+
+    /// Insert content at the end of the element.
+    pub fn append<C: Into<Content>>(&self, content: C) -> Result<(), Error> {
+        insert(self, Position::BeforeEnd, &content.into(), false)
+    }
+
+    /// Insert content at the beginning of the element.
+    pub fn prepend<C: Into<Content>>(&self, content: C) -> Result<(), Error> {
+        insert(self, Position::AfterBegin, &content.into(), false)
+    }
+
+    /// Insert content after the element.
+    pub fn after<C: Into<Content>>(&self, content: C) -> Result<(), Error> {
+        insert(self, Position::AfterEnd, &content.into(), false)
+    }
+
+    /// Insert content before the element.
+    pub fn before<C: Into<Content>>(&self, content: C) -> Result<(), Error> {
+        insert(self, Position::BeforeBegin, &content.into(), false)
+    }
+
+    /// Insert the element at the end of the targets.
+    pub fn append_to<'a, T: Into<Target<'a>>>(&self, target: T) -> Result<(), Error> {
+        target.into().resolve(Some(self))?.append(self)
+    }
+
+    /// Insert the element at the beginning of the targets.
+    pub fn prepend_to<'a, T: Into<Target<'a>>>(&self, target: T) -> Result<(), Error> {
+        target.into().resolve(Some(self))?.prepend(self)
+    }
+
+    /// Insert the element after the targets.
+    pub fn insert_after<'a, T: Into<Target<'a>>>(&self, target: T) -> Result<(), Error> {
+        target.into().resolve(Some(self))?.after(self)
+    }
+
+    /// Insert the element before the targets.
+    pub fn insert_before<'a, T: Into<Target<'a>>>(&self, target: T) -> Result<(), Error> {
+        target.into().resolve(Some(self))?.before(self)
+    }
+
+    /// Create a deep copy of the element.
+    ///
+    /// Unlike `clone`, which returns another reference to the same
+    /// element, this clones the DOM nodes.  The event listeners and
+    /// data that were added by this crate are copied to the element
+    /// if `with_data_and_events` is set and to all of its descendants
+    /// if `deep_with_data_and_events` is set as well.  As in jQuery,
+    /// the second flag is ignored without the first.
+    pub fn clone_node(
+        &self,
+        with_data_and_events: bool,
+        deep_with_data_and_events: bool,
+    ) -> Result<Element, Error> {
+        let clone = self
+            .0
+            .clone_node_with_deep(true)?
+            .dyn_into::<web_sys::Element>()
+            .map(Element::from)
+            .map_err(|_| Error::DynRefFailed)?;
+
+        if with_data_and_events {
+            copy_data_and_events(self, &clone)?;
+        }
+        if with_data_and_events && deep_with_data_and_events {
+            let descendants = self.descendants().into_iter().skip(1);
+            for (element, copy) in descendants.zip(clone.descendants().into_iter().skip(1)) {
+                copy_data_and_events(&element, &copy)?;
+            }
+        }
+
+        Ok(clone)
+    }
+
+    /// Remove the element from the DOM.
+    ///
+    /// The event listeners and data of the element and its
+    /// descendants that were added by this crate are removed as well.
+    pub fn remove(&self) {
+        clean(self);
+        self.0.remove()
+    }
+
+    /// Remove the element from the DOM but keep its event listeners
+    /// and data, so it can be inserted again.
+    pub fn detach(&self) {
+        self.0.remove()
+    }
+
+    /// Remove all child nodes of the element.
+    pub fn empty(&self) {
+        Collection::from(self.0.children()).iter().for_each(clean);
+        self.0.set_text_content(None)
+    }
+
+    /// Replace the element with the content.
+    pub fn replace_with<C: Into<Content>>(&self, content: C) -> Result<(), Error> {
+        self.before(content)?;
+        self.remove();
+        Ok(())
+    }
+
+    /// Replace the targets with the element.
+    pub fn replace_all<'a, T: Into<Target<'a>>>(&self, target: T) -> Result<(), Error> {
+        target.into().resolve(Some(self))?.replace_with(self)
+    }
+
+    /// Wrap the element with a clone of the wrapper.
+    ///
+    /// The wrapper is an element, the first element of a collection,
+    /// or HTML.  The element is inserted into the innermost
+    /// descendant of the wrapper structure.
+    pub fn wrap<C: Into<Content>>(&self, wrapper: C) -> Result<(), Error> {
+        let wrapper = create_wrapper(&wrapper.into(), self)?;
+        if self.0.parent_node().is_some() {
+            self.before(&wrapper)?;
+        }
+        innermost(wrapper).append(self)
+    }
+
+    /// Wrap the element, like `wrap` for a single element.
+    pub fn wrap_all<C: Into<Content>>(&self, wrapper: C) -> Result<(), Error> {
+        self.wrap(wrapper)
+    }
+
+    /// Wrap the contents of the element with a clone of the wrapper.
+    pub fn wrap_inner<C: Into<Content>>(&self, wrapper: C) -> Result<(), Error> {
+        let wrapper = create_wrapper(&wrapper.into(), self)?;
+        let contents = js_sys::Array::from(&self.0.child_nodes());
+        self.append(&wrapper)?;
+        innermost(wrapper)
+            .append_with_node(&contents)
+            .map_err(Into::into)
+    }
+
+    /// Remove the parent of the element and keep its children in
+    /// place, if the parent matches the optional selectors.
+    pub fn unwrap(&self, selectors: Option<&str>) -> Result<(), Error> {
+        Collection::from(self.clone()).unwrap(selectors)
+    }
 }
 
 /// DOM manipulation
@@ -30,4 +383,196 @@ impl Collection {
             elem.set_text(text).ok();
         })
     }
+
+    /// Insert content at the end of every element.
+    ///
+    /// Elements are cloned for every element but the last one.
+    pub fn append<C: Into<Content>>(&self, content: C) -> Result<(), Error> {
+        insert_all(self.0.iter(), Position::BeforeEnd, &content.into())
+    }
+
+    /// Insert content at the beginning of every element.
+    pub fn prepend<C: Into<Content>>(&self, content: C) -> Result<(), Error> {
+        insert_all(self.0.iter(), Position::AfterBegin, &content.into())
+    }
+
+    /// Insert content after every element.
+    pub fn after<C: Into<Content>>(&self, content: C) -> Result<(), Error> {
+        insert_all(self.0.iter(), Position::AfterEnd, &content.into())
+    }
+
+    /// Insert content before every element.
+    pub fn before<C: Into<Content>>(&self, content: C) -> Result<(), Error> {
+        insert_all(self.0.iter(), Position::BeforeBegin, &content.into())
+    }
+
+    /// Insert the elements at the end of the targets.
+    pub fn append_to<'a, T: Into<Target<'a>>>(&self, target: T) -> Result<(), Error> {
+        target.into().resolve(self.0.front())?.append(self)
+    }
+
+    /// Insert the elements at the beginning of the targets.
+    pub fn prepend_to<'a, T: Into<Target<'a>>>(&self, target: T) -> Result<(), Error> {
+        target.into().resolve(self.0.front())?.prepend(self)
+    }
+
+    /// Insert the elements after the targets.
+    pub fn insert_after<'a, T: Into<Target<'a>>>(&self, target: T) -> Result<(), Error> {
+        target.into().resolve(self.0.front())?.after(self)
+    }
+
+    /// Insert the elements before the targets.
+    pub fn insert_before<'a, T: Into<Target<'a>>>(&self, target: T) -> Result<(), Error> {
+        target.into().resolve(self.0.front())?.before(self)
+    }
+
+    /// Create deep copies of the elements.
+    pub fn clone_node(
+        &self,
+        with_data_and_events: bool,
+        deep_with_data_and_events: bool,
+    ) -> Result<Collection, Error> {
+        self.0
+            .iter()
+            .map(|elem| elem.clone_node(with_data_and_events, deep_with_data_and_events))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Into::into)
+    }
+
+    pub fn remove(&self) {
+        self.0.iter().for_each(Element::remove)
+    }
+
+    pub fn detach(&self) {
+        self.0.iter().for_each(Element::detach)
+    }
+
+    pub fn empty(&self) {
+        self.0.iter().for_each(Element::empty)
+    }
+
+    /// Replace every element with the content.
+    ///
+    /// Elements are cloned for every element but the last one.
+    pub fn replace_with<C: Into<Content>>(&self, content: C) -> Result<(), Error> {
+        self.before(content)?;
+        self.remove();
+        Ok(())
+    }
+
+    /// Replace the targets with the elements.
+    pub fn replace_all<'a, T: Into<Target<'a>>>(&self, target: T) -> Result<(), Error> {
+        target.into().resolve(self.0.front())?.replace_with(self)
+    }
+
+    /// Wrap every element with a clone of the wrapper.
+    pub fn wrap<C: Into<Content>>(&self, wrapper: C) -> Result<(), Error> {
+        let wrapper = wrapper.into();
+        for element in self.0.iter() {
+            element.wrap(wrapper.clone())?;
+        }
+
+        Ok(())
+    }
+
+    /// Wrap all elements with a single clone of the wrapper.
+    ///
+    /// The wrapper is inserted before the first element and all
+    /// elements are moved into it.
+    pub fn wrap_all<C: Into<Content>>(&self, wrapper: C) -> Result<(), Error> {
+        let first = match self.0.front() {
+            Some(first) => first,
+            None => return Ok(()),
+        };
+        let wrapper = create_wrapper(&wrapper.into(), first)?;
+        if first.0.parent_node().is_some() {
+            first.before(&wrapper)?;
+        }
+        innermost(wrapper).append(self)
+    }
+
+    /// Wrap the contents of every element with a clone of the wrapper.
+    pub fn wrap_inner<C: Into<Content>>(&self, wrapper: C) -> Result<(), Error> {
+        let wrapper = wrapper.into();
+        for element in self.0.iter() {
+            element.wrap_inner(wrapper.clone())?;
+        }
+
+        Ok(())
+    }
+
+    /// Remove the parents of the elements and keep their children in
+    /// place, if the parents match the optional selectors.
+    pub fn unwrap(&self, selectors: Option<&str>) -> Result<(), Error> {
+        let mut parents = Collection::new();
+        for parent in self.parent() {
+            if parents.iter().any(|elem| elem.0 == parent.0)
+                || parent.is("body")?
+                || !selectors.map(|s| parent.is(s)).transpose()?.unwrap_or(true)
+            {
+                continue;
+            }
+            parents.push_back(parent);
+        }
+
+        for parent in parents {
+            let children = js_sys::Array::from(&parent.0.child_nodes());
+            parent.before_with_node(&children)?;
+            parent.remove();
+        }
+
+        Ok(())
+    }
+}
+
+/// Create a new wrapper structure for the element.
+///
+/// Wrapper elements are deep cloned, HTML is parsed in the document
+/// of the element.
+fn create_wrapper(wrapper: &Content, element: &Element) -> Result<Element, Error> {
+    let wrapper = match wrapper {
+        Content::Element(wrapper) => Some(wrapper),
+        Content::Collection(collection) => collection.front(),
+        Content::Html(html) => {
+            return element
+                .owner_document()
+                .map(Document::from)
+                .ok_or(Error::NoDocumentElement)?
+                .create(html)?
+                .pop_front()
+                .ok_or(Error::NoWrapperElement)
+        }
+        Content::Text(_) => None,
+    }
+    .ok_or(Error::NoWrapperElement)?;
+
+    wrapper
+        .clone_node_with_deep(true)?
+        .dyn_into::<web_sys::Element>()
+        .map(Into::into)
+        .map_err(|_| Error::DynRefFailed)
+}
+
+/// Return the innermost first descendant of the element.
+fn innermost(mut element: Element) -> Element {
+    while let Some(child) = element.0.first_element_child() {
+        element = child.into();
+    }
+    element
+}
+
+/// Copy the event listeners and data of the element.
+fn copy_data_and_events(from: &Element, to: &Element) -> Result<(), Error> {
+    listener::copy(from.0.as_ref(), to.0.as_ref())?;
+    data::copy(from, to);
+    Ok(())
+}
+
+/// Remove the event listeners and data of the element and its
+/// descendants.
+fn clean(element: &Element) {
+    for element in element.descendants() {
+        listener::remove(element.0.as_ref(), None, &[]);
+        element.remove_data(None);
+    }
 }
@@ -1,9 +1,20 @@
 //! The main `Query` interface.
 
 mod attributes;
+mod builder;
+mod context;
+mod css;
+mod data;
+mod dimensions;
 mod events;
 mod helpers;
+mod listener;
 mod manipulation;
+mod offset;
+mod shortcut;
+#[cfg(feature = "futures")]
+mod stream;
+mod timing;
 mod traversing;
 
 use crate::error::Error;
@@ -17,8 +28,54 @@ use std::{
 use wasm_bindgen::JsCast;
 use web_sys::{HtmlCollection, HtmlElement, NodeList};
 
-pub use events::Event;
+pub use builder::ElementBuilder;
+pub use context::EventContext;
+pub use css::CSS_NUMBER;
+pub use events::{Event, EventType, TriggerOptions};
 pub use helpers::{FormData, FormValue};
+pub use listener::{EventListener, ListenerOptions};
+pub use manipulation::{Content, Target};
+pub use offset::{Offset, ScrollOptions};
+pub use shortcut::{Shortcut, ShortcutOptions};
+#[cfg(feature = "futures")]
+pub use stream::{EventStream, NextEvent};
+pub use timing::{debounce, debounce_with, throttle, throttle_with, TimerOptions};
+
+/// Window with jQuery-like methods.
+#[derive(AsRef, Clone, Debug, Deref, DerefMut, From, Into)]
+pub struct Window(web_sys::Window);
+
+impl Window {
+    pub fn new() -> Result<Self, Error> {
+        let inner = web_sys::window().ok_or(Error::DomElementNotFound("window"))?;
+
+        Ok(Self(inner))
+    }
+
+    pub fn document(&self) -> Result<Document, Error> {
+        self.0
+            .document()
+            .map(Document)
+            .ok_or(Error::DomElementNotFound("document"))
+    }
+
+    /// Return the inner width and height of the window's viewport.
+    pub fn inner_size(&self) -> Result<(f64, f64), Error> {
+        let width = self.0.inner_width()?.as_f64();
+        let height = self.0.inner_height()?.as_f64();
+        width.zip(height).ok_or(Error::NoValue("inner_size"))
+    }
+
+    /// Return the horizontal and vertical scroll position.
+    pub fn scroll_xy(&self) -> Result<(f64, f64), Error> {
+        Ok((self.0.scroll_x()?, self.0.scroll_y()?))
+    }
+
+    /// Return the ratio of physical to CSS pixels.
+    pub fn device_pixel_ratio(&self) -> f64 {
+        self.0.device_pixel_ratio()
+    }
+}
 
 /// Document with jQuery-like methods.
 #[derive(AsRef, Clone, Debug, Deref, DerefMut, From, Into)]
@@ -26,12 +83,7 @@ pub struct Document(web_sys::Document);
 
 impl Document {
     pub fn new() -> Result<Self, Error> {
-        let inner = web_sys::window()
-            .ok_or(Error::DomElementNotFound("window"))?
-            .document()
-            .ok_or(Error::DomElementNotFound("document"))?;
-
-        Ok(Self(inner))
+        Window::new()?.document()
     }
 
     pub fn descendants(&self) -> Collection {
@@ -196,7 +248,7 @@ impl From<Element> for Collection {
 
 impl FromIterator<Collection> for Collection {
     fn from_iter<I: IntoIterator<Item = Collection>>(iter: I) -> Self {
-        iter.into_iter().map(|coll| coll.0).flatten().collect()
+        iter.into_iter().flat_map(|coll| coll.0).collect()
     }
 }
 
@@ -0,0 +1,197 @@
+//! Offset and scroll position
+
+use crate::{
+    error::Error,
+    query::{Collection, Element},
+};
+use web_sys::{ScrollBehavior, ScrollIntoViewOptions, ScrollLogicalPosition};
+
+/// Coordinates of an element.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Offset {
+    pub top: f64,
+    pub left: f64,
+}
+
+impl Offset {
+    pub fn new(top: f64, left: f64) -> Self {
+        Self { top, left }
+    }
+}
+
+/// Options of `scroll_into_view_with`.
+#[derive(Clone, Debug, Default)]
+pub struct ScrollOptions {
+    smooth: bool,
+    block: Option<ScrollLogicalPosition>,
+    inline: Option<ScrollLogicalPosition>,
+}
+
+impl ScrollOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Scroll smoothly instead of jumping to the position.
+    pub fn smooth(mut self, smooth: bool) -> Self {
+        self.smooth = smooth;
+        self
+    }
+
+    /// Vertical alignment, the default is `Start`.
+    pub fn block(mut self, block: ScrollLogicalPosition) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Horizontal alignment, the default is `Nearest`.
+    pub fn inline(mut self, inline: ScrollLogicalPosition) -> Self {
+        self.inline = Some(inline);
+        self
+    }
+}
+
+/// Offset and scroll position
+impl Element {
+    /// Return the coordinates of the element relative to the document.
+    pub fn offset(&self) -> Offset {
+        if self.get_client_rects().length() == 0 {
+            return Offset::default();
+        }
+        let rect = self.get_bounding_client_rect();
+        let (x, y) = self
+            .owner_document()
+            .and_then(|document| document.default_view())
+            .map(|window| {
+                (
+                    window.page_x_offset().unwrap_or_default(),
+                    window.page_y_offset().unwrap_or_default(),
+                )
+            })
+            .unwrap_or_default();
+        Offset::new(rect.top() + y, rect.left() + x)
+    }
+
+    /// Move the element to the coordinates relative to the document.
+    ///
+    /// Elements with a `static` position are changed to `relative`.
+    pub fn set_offset(&self, offset: Offset) -> Result<(), Error> {
+        if self.css("position")? == "static" {
+            self.set_css("position", "relative")?;
+        }
+        let current = self.offset();
+        let top = css_f64(&self.css("top")?);
+        let left = css_f64(&self.css("left")?);
+        self.set_css("top", &(offset.top - current.top + top).to_string())?;
+        self.set_css("left", &(offset.left - current.left + left).to_string())
+    }
+
+    /// Return the coordinates of the element relative to its offset
+    /// parent, excluding the margin of the element.
+    ///
+    /// Like `offsetTop`, the position does not change when the
+    /// offset parent is scrolled.
+    pub fn position(&self) -> Result<Offset, Error> {
+        let (offset, parent) = if self.css("position")? == "fixed" {
+            let rect = self.get_bounding_client_rect();
+            (Offset::new(rect.top(), rect.left()), Offset::default())
+        } else {
+            let parent = match self.offset_parent() {
+                Some(parent) if parent.local_name() != "html" => {
+                    let offset = parent.offset();
+                    Offset::new(
+                        offset.top + css_f64(&parent.css("border-top-width")?)
+                            - parent.scroll_top(),
+                        offset.left + css_f64(&parent.css("border-left-width")?)
+                            - parent.scroll_left(),
+                    )
+                }
+                _ => Offset::default(),
+            };
+            (self.offset(), parent)
+        };
+
+        Ok(Offset::new(
+            offset.top - parent.top - css_f64(&self.css("margin-top")?),
+            offset.left - parent.left - css_f64(&self.css("margin-left")?),
+        ))
+    }
+
+    /// Return the vertical scroll position.
+    pub fn scroll_top(&self) -> f64 {
+        self.0.scroll_top() as f64
+    }
+
+    /// Set the vertical scroll position.
+    pub fn set_scroll_top(&self, value: f64) {
+        self.0.set_scroll_top(value as i32)
+    }
+
+    /// Return the horizontal scroll position.
+    pub fn scroll_left(&self) -> f64 {
+        self.0.scroll_left() as f64
+    }
+
+    /// Set the horizontal scroll position.
+    pub fn set_scroll_left(&self, value: f64) {
+        self.0.set_scroll_left(value as i32)
+    }
+
+    /// Scroll the element's ancestors to make the element visible.
+    pub fn scroll_into_view_with(&self, options: &ScrollOptions) {
+        let init = ScrollIntoViewOptions::new();
+        if options.smooth {
+            init.set_behavior(ScrollBehavior::Smooth);
+        }
+        if let Some(block) = options.block {
+            init.set_block(block);
+        }
+        if let Some(inline) = options.inline {
+            init.set_inline(inline);
+        }
+        self.0.scroll_into_view_with_scroll_into_view_options(&init)
+    }
+}
+
+/// Offset and scroll position
+impl Collection {
+    pub fn offset(&self) -> Vec<Offset> {
+        self.0.iter().map(|elem| elem.offset()).collect()
+    }
+
+    pub fn set_offset(&self, offset: Offset) -> Result<(), Error> {
+        for element in self.0.iter() {
+            element.set_offset(offset)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn position(&self) -> Result<Vec<Offset>, Error> {
+        self.0.iter().map(|elem| elem.position()).collect()
+    }
+
+    pub fn scroll_top(&self) -> Vec<f64> {
+        self.0.iter().map(|elem| elem.scroll_top()).collect()
+    }
+
+    pub fn set_scroll_top(&self, value: f64) {
+        self.0.iter().for_each(|elem| elem.set_scroll_top(value))
+    }
+
+    pub fn scroll_left(&self) -> Vec<f64> {
+        self.0.iter().map(|elem| elem.scroll_left()).collect()
+    }
+
+    pub fn set_scroll_left(&self, value: f64) {
+        self.0.iter().for_each(|elem| elem.set_scroll_left(value))
+    }
+}
+
+/// Parse a CSS pixel value, `auto` or invalid values are 0.
+fn css_f64(value: &str) -> f64 {
+    value
+        .trim_end_matches("px")
+        .parse::<f64>()
+        .unwrap_or_default()
+}
@@ -0,0 +1,287 @@
+//! Keyboard shortcuts
+
+use crate::{
+    error::Error,
+    query::{Document, Element, EventListener},
+};
+use std::{cell::Cell, convert::TryFrom, str::FromStr, time::Duration};
+use web_sys::KeyboardEvent;
+
+/// Keyboard shortcut, such as `"Ctrl+Shift+K"` or the sequence `"g i"`.
+///
+/// A shortcut is one or more key combinations separated by spaces
+/// that have to be pressed one after another.  Each combination is a
+/// key with optional `Ctrl`, `Alt`, `Shift` and `Meta` modifiers
+/// separated by `+`.  The `Mod` modifier is `Meta` (Cmd) on macOS and
+/// `Ctrl` on other platforms.  Keys are the `KeyboardEvent.key`
+/// values, compared case-insensitively.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Shortcut(Vec<KeyCombo>);
+
+impl Shortcut {
+    /// Return true if the event matches the shortcut with a single
+    /// key combination.
+    pub fn matches(&self, event: &KeyboardEvent) -> bool {
+        self.0.len() == 1 && self.0[0].matches(event)
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = Error;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let combos = spec
+            .split_whitespace()
+            .map(KeyCombo::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        if combos.is_empty() {
+            return Err(Error::InvalidShortcut(spec.to_string()));
+        }
+        Ok(Self(combos))
+    }
+}
+
+impl TryFrom<&str> for Shortcut {
+    type Error = Error;
+
+    fn try_from(spec: &str) -> Result<Self, Self::Error> {
+        spec.parse()
+    }
+}
+
+/// Key with modifiers.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct KeyCombo {
+    key: String,
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    meta: bool,
+}
+
+impl KeyCombo {
+    fn matches(&self, event: &KeyboardEvent) -> bool {
+        // Shifted symbols like `?` depend on the keyboard layout,
+        // so `Shift` is only compared if it is required by the
+        // shortcut or if the key is a letter or a named key.
+        let mut chars = self.key.chars();
+        let symbol = matches!((chars.next(), chars.next()), (Some(c), None) if !c.is_alphabetic());
+
+        event.key().to_lowercase() == self.key
+            && event.ctrl_key() == self.ctrl
+            && event.alt_key() == self.alt
+            && event.meta_key() == self.meta
+            && (event.shift_key() == self.shift || (symbol && !self.shift))
+    }
+}
+
+impl FromStr for KeyCombo {
+    type Err = Error;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidShortcut(spec.to_string());
+        let mut combo = KeyCombo::default();
+
+        // Split at `+` but allow `+` as the key, e.g. `Ctrl++`.
+        let (modifiers, key) = match spec.strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None if spec == "+" => ("", "+"),
+            None => match spec.rfind('+') {
+                Some(pos) => (&spec[..pos], &spec[pos + 1..]),
+                None => ("", spec),
+            },
+        };
+
+        for modifier in modifiers.split('+').filter(|s| !s.is_empty()) {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => combo.ctrl = true,
+                "alt" | "option" => combo.alt = true,
+                "shift" => combo.shift = true,
+                "meta" | "cmd" | "command" | "super" => combo.meta = true,
+                "mod" if is_mac() => combo.meta = true,
+                "mod" => combo.ctrl = true,
+                _ => return Err(invalid()),
+            }
+        }
+
+        combo.key = match key.to_lowercase().as_str() {
+            "" => return Err(invalid()),
+            "space" => " ".to_string(),
+            "esc" => "escape".to_string(),
+            "del" => "delete".to_string(),
+            "up" | "down" | "left" | "right" => format!("arrow{}", key.to_lowercase()),
+            key => key.to_string(),
+        };
+
+        Ok(combo)
+    }
+}
+
+/// Options of keyboard shortcuts.
+#[derive(Clone, Debug)]
+pub struct ShortcutOptions {
+    ignore_inputs: bool,
+    prevent_default: bool,
+    timeout: Duration,
+}
+
+impl Default for ShortcutOptions {
+    fn default() -> Self {
+        Self {
+            ignore_inputs: true,
+            prevent_default: true,
+            timeout: Duration::from_secs(1),
+        }
+    }
+}
+
+impl ShortcutOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Ignore key events from inputs, textareas, selects and
+    /// editable content, the default is `true`.
+    pub fn ignore_inputs(mut self, ignore_inputs: bool) -> Self {
+        self.ignore_inputs = ignore_inputs;
+        self
+    }
+
+    /// Prevent the default action of matched shortcuts, the default
+    /// is `true`.
+    pub fn prevent_default(mut self, prevent_default: bool) -> Self {
+        self.prevent_default = prevent_default;
+        self
+    }
+
+    /// Maximum time between the keys of a sequence, the default is
+    /// one second.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// Keyboard shortcuts
+impl Element {
+    /// Bind a keyboard shortcut to the element and its descendants.
+    pub fn shortcut<F>(&self, shortcut: &str, handler: F) -> Result<EventListener, Error>
+    where
+        F: FnMut(KeyboardEvent) + 'static,
+    {
+        self.shortcut_with(shortcut, &ShortcutOptions::default(), handler)
+    }
+
+    /// Bind a keyboard shortcut with options.
+    pub fn shortcut_with<F>(
+        &self,
+        shortcut: &str,
+        options: &ShortcutOptions,
+        handler: F,
+    ) -> Result<EventListener, Error>
+    where
+        F: FnMut(KeyboardEvent) + 'static,
+    {
+        self.on_key_down(key_down(shortcut.parse()?, options, handler))
+    }
+}
+
+/// Keyboard shortcuts
+impl Document {
+    /// Bind a global keyboard shortcut to the document.
+    pub fn shortcut<F>(&self, shortcut: &str, handler: F) -> Result<EventListener, Error>
+    where
+        F: FnMut(KeyboardEvent) + 'static,
+    {
+        self.shortcut_with(shortcut, &ShortcutOptions::default(), handler)
+    }
+
+    /// Bind a global keyboard shortcut with options.
+    pub fn shortcut_with<F>(
+        &self,
+        shortcut: &str,
+        options: &ShortcutOptions,
+        handler: F,
+    ) -> Result<EventListener, Error>
+    where
+        F: FnMut(KeyboardEvent) + 'static,
+    {
+        self.on_key_down(key_down(shortcut.parse()?, options, handler))
+    }
+}
+
+/// Create the `keydown` handler of a shortcut.
+///
+/// The handler tracks the position in the key sequence, which is
+/// reset if another key is pressed or the timeout expired.
+fn key_down<F>(
+    shortcut: Shortcut,
+    options: &ShortcutOptions,
+    mut handler: F,
+) -> impl FnMut(KeyboardEvent) + 'static
+where
+    F: FnMut(KeyboardEvent) + 'static,
+{
+    let options = options.clone();
+    let position = Cell::new(0);
+    let last = Cell::new(0.0);
+
+    move |event: KeyboardEvent| {
+        if event.repeat() || is_modifier(&event.key()) {
+            return;
+        }
+        if options.ignore_inputs && is_input(&event) {
+            return;
+        }
+
+        let now = js_sys::Date::now();
+        if now - last.get() > options.timeout.as_millis() as f64 {
+            position.set(0);
+        }
+        last.set(now);
+
+        let combos = &shortcut.0;
+        let next = if combos[position.get()].matches(&event) {
+            position.get() + 1
+        } else if combos[0].matches(&event) {
+            1
+        } else {
+            0
+        };
+
+        if next == combos.len() {
+            position.set(0);
+            if options.prevent_default {
+                event.prevent_default();
+            }
+            handler(event);
+        } else {
+            position.set(next);
+        }
+    }
+}
+
+fn is_modifier(key: &str) -> bool {
+    matches!(key, "Alt" | "Control" | "Meta" | "Shift" | "OS")
+}
+
+/// Return true if the event target is an editable element.
+fn is_input(event: &KeyboardEvent) -> bool {
+    let element = match Element::try_from(web_sys::Event::from(event.clone())) {
+        Ok(element) => element,
+        Err(_) => return false,
+    };
+    element.is("input, textarea, select").unwrap_or_default()
+        || element
+            .dyn_ref::<web_sys::HtmlElement>()
+            .map(|html| html.is_content_editable())
+            .unwrap_or_default()
+}
+
+/// Return true if `Mod` is the `Meta` (Cmd) key on the platform.
+fn is_mac() -> bool {
+    web_sys::window()
+        .and_then(|window| window.navigator().platform().ok())
+        .map(|platform| platform.contains("Mac") || platform.starts_with("iP"))
+        .unwrap_or_default()
+}
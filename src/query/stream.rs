@@ -0,0 +1,158 @@
+//! Async event streams
+
+use crate::{
+    error::Error,
+    query::{Collection, Element, EventListener, EventType},
+};
+use futures_core::Stream;
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+#[derive(Default)]
+struct Queue {
+    events: VecDeque<web_sys::Event>,
+    waker: Option<Waker>,
+    closed: bool,
+}
+
+/// Sending side of the queue that is owned by the listener callback.
+///
+/// The queue is closed when the listener is removed, e.g. by `off`,
+/// and the callback is dropped.
+struct Sender(Rc<RefCell<Queue>>);
+
+impl Sender {
+    fn send(&self, event: web_sys::Event) {
+        let mut queue = self.0.borrow_mut();
+        queue.events.push_back(event);
+        if let Some(waker) = queue.waker.take() {
+            waker.wake()
+        }
+    }
+}
+
+impl Drop for Sender {
+    fn drop(&mut self) {
+        let mut queue = self.0.borrow_mut();
+        queue.closed = true;
+        if let Some(waker) = queue.waker.take() {
+            waker.wake()
+        }
+    }
+}
+
+/// Stream of events.
+///
+/// The stream owns the event listener and removes it when it is
+/// dropped.  It ends when the listener is removed otherwise.
+#[must_use = "streams do nothing unless polled"]
+pub struct EventStream {
+    queue: Rc<RefCell<Queue>>,
+    _listener: EventListener,
+}
+
+impl EventStream {
+    /// Create a stream with a listener that sends its events.
+    fn new<F>(add: F) -> Result<Self, Error>
+    where
+        F: FnOnce(Sender) -> Result<EventListener, Error>,
+    {
+        let queue = Rc::new(RefCell::new(Queue::default()));
+        let listener = add(Sender(queue.clone()))?;
+
+        Ok(Self {
+            queue,
+            _listener: listener,
+        })
+    }
+}
+
+impl Stream for EventStream {
+    type Item = web_sys::Event;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut queue = self.queue.borrow_mut();
+        if let Some(event) = queue.events.pop_front() {
+            Poll::Ready(Some(event))
+        } else if queue.closed {
+            Poll::Ready(None)
+        } else {
+            queue.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// Future of the next event.
+#[must_use = "futures do nothing unless polled"]
+pub struct NextEvent(Option<Result<EventStream, Error>>);
+
+impl Future for NextEvent {
+    type Output = Result<web_sys::Event, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let result = match self.0.take() {
+            Some(Ok(mut stream)) => match Pin::new(&mut stream).poll_next(cx) {
+                Poll::Ready(event) => event.ok_or(Error::ListenerRemoved),
+                Poll::Pending => {
+                    self.0 = Some(Ok(stream));
+                    return Poll::Pending;
+                }
+            },
+            Some(Err(err)) => Err(err),
+            None => Err(Error::ListenerRemoved),
+        };
+        Poll::Ready(result)
+    }
+}
+
+/// Async events
+impl Element {
+    /// Return a stream of events.
+    pub fn events<E>(&self, event: E) -> Result<EventStream, Error>
+    where
+        E: Into<EventType>,
+    {
+        EventStream::new(|sender| self.on(event, move |event| sender.send(event)))
+    }
+
+    /// Wait for the next event.
+    ///
+    /// The listener is added immediately and not when the future is
+    /// polled for the first time.
+    pub fn next_event<E>(&self, event: E) -> NextEvent
+    where
+        E: Into<EventType>,
+    {
+        NextEvent(Some(EventStream::new(|sender| {
+            self.one(event, move |event| sender.send(event))
+        })))
+    }
+}
+
+/// Async events
+impl Collection {
+    /// Return a stream of events of all elements in the collection.
+    pub fn events<E>(&self, event: E) -> Result<EventStream, Error>
+    where
+        E: Into<EventType>,
+    {
+        EventStream::new(|sender| self.on(event, move |event| sender.send(event)))
+    }
+
+    /// Wait for the next event on any element in the collection.
+    pub fn next_event<E>(&self, event: E) -> NextEvent
+    where
+        E: Into<EventType>,
+    {
+        NextEvent(Some(EventStream::new(|sender| {
+            self.one_for_all(event, move |event| sender.send(event))
+        })))
+    }
+}
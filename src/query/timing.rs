@@ -0,0 +1,250 @@
+//! Debounced and throttled event handlers
+
+use std::{
+    cell::RefCell,
+    convert::TryFrom,
+    rc::{Rc, Weak},
+    time::Duration,
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+
+/// Options of debounced and throttled handlers.
+///
+/// The handler can be called on the leading edge, when the first
+/// event arrives, and on the trailing edge, after the wait time with
+/// the last event.
+#[derive(Clone, Debug)]
+pub struct TimerOptions {
+    leading: Option<bool>,
+    trailing: bool,
+}
+
+impl Default for TimerOptions {
+    fn default() -> Self {
+        Self {
+            leading: None,
+            trailing: true,
+        }
+    }
+}
+
+impl TimerOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Call the handler on the leading edge.
+    ///
+    /// The default is `false` for `debounce` and `true` for
+    /// `throttle`.
+    pub fn leading(mut self, leading: bool) -> Self {
+        self.leading = Some(leading);
+        self
+    }
+
+    /// Call the handler on the trailing edge, the default is `true`.
+    pub fn trailing(mut self, trailing: bool) -> Self {
+        self.trailing = trailing;
+        self
+    }
+}
+
+/// Debounce the handler.
+///
+/// The handler is called after no event arrived for the wait time.
+/// A pending call is cancelled when the returned handler is dropped,
+/// for example when the `EventListener` is removed.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use web_sys_query::{debounce, Element, Error, Event, EventListener};
+///
+/// fn search(input: &Element) -> Result<EventListener, Error> {
+///     input.on(
+///         Event::Input,
+///         debounce(Duration::from_millis(300), |_| log::info!("search")),
+///     )
+/// }
+/// # fn main() {}
+/// ```
+pub fn debounce<T, F>(wait: Duration, handler: F) -> impl FnMut(T) + 'static
+where
+    T: 'static,
+    F: FnMut(T) + 'static,
+{
+    debounce_with(wait, &TimerOptions::default(), handler)
+}
+
+/// Debounce the handler with the specified options.
+pub fn debounce_with<T, F>(
+    wait: Duration,
+    options: &TimerOptions,
+    handler: F,
+) -> impl FnMut(T) + 'static
+where
+    T: 'static,
+    F: FnMut(T) + 'static,
+{
+    let timer = TimerHandle::new(Mode::Debounce, wait, options, handler);
+    move |event| timer.call(event)
+}
+
+/// Throttle the handler.
+///
+/// The handler is called at most once per wait time.  A pending call
+/// is cancelled when the returned handler is dropped.
+pub fn throttle<T, F>(wait: Duration, handler: F) -> impl FnMut(T) + 'static
+where
+    T: 'static,
+    F: FnMut(T) + 'static,
+{
+    throttle_with(wait, &TimerOptions::default(), handler)
+}
+
+/// Throttle the handler with the specified options.
+pub fn throttle_with<T, F>(
+    wait: Duration,
+    options: &TimerOptions,
+    handler: F,
+) -> impl FnMut(T) + 'static
+where
+    T: 'static,
+    F: FnMut(T) + 'static,
+{
+    let timer = TimerHandle::new(Mode::Throttle, wait, options, handler);
+    move |event| timer.call(event)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Debounce,
+    Throttle,
+}
+
+/// Shared handler state of a debounced or throttled handler.
+struct Timer<T, F> {
+    mode: Mode,
+    wait: i32,
+    leading: bool,
+    trailing: bool,
+    handler: RefCell<F>,
+    state: RefCell<State<T>>,
+}
+
+struct State<T> {
+    timeout: Option<i32>,
+    pending: Option<T>,
+    closure: Option<Closure<dyn FnMut()>>,
+}
+
+/// Handle of the timer that cancels it when it is dropped.
+struct TimerHandle<T, F>(Rc<Timer<T, F>>);
+
+impl<T, F> Timer<T, F>
+where
+    T: 'static,
+    F: FnMut(T) + 'static,
+{
+    fn call(&self, event: T) {
+        let mut state = self.state.borrow_mut();
+        let running = state.timeout.is_some();
+        let leading = !running && self.leading;
+
+        if self.mode == Mode::Debounce || !running {
+            self.start(&mut state);
+        }
+        if leading {
+            state.pending = None;
+            drop(state);
+            self.fire(event);
+        } else if self.trailing {
+            state.pending = Some(event);
+        }
+    }
+
+    fn expire(&self) {
+        let mut state = self.state.borrow_mut();
+        state.timeout = None;
+        if let Some(event) = state.pending.take() {
+            // Throttled handlers wait again after each call.
+            if self.mode == Mode::Throttle {
+                self.start(&mut state);
+            }
+            drop(state);
+            self.fire(event);
+        }
+    }
+
+    fn start(&self, state: &mut State<T>) {
+        let window = match web_sys::window() {
+            Some(window) => window,
+            None => return,
+        };
+        if let Some(timeout) = state.timeout.take() {
+            window.clear_timeout_with_handle(timeout);
+        }
+        state.timeout = state.closure.as_ref().and_then(|closure| {
+            window
+                .set_timeout_with_callback_and_timeout_and_arguments_0(
+                    closure.as_ref().unchecked_ref(),
+                    self.wait,
+                )
+                .ok()
+        });
+    }
+
+    fn fire(&self, event: T) {
+        if let Ok(mut handler) = self.handler.try_borrow_mut() {
+            (*handler)(event)
+        }
+    }
+}
+
+impl<T, F> TimerHandle<T, F>
+where
+    T: 'static,
+    F: FnMut(T) + 'static,
+{
+    fn new(mode: Mode, wait: Duration, options: &TimerOptions, handler: F) -> Self {
+        let timer = Rc::new(Timer {
+            mode,
+            wait: i32::try_from(wait.as_millis()).unwrap_or(i32::MAX),
+            leading: options.leading.unwrap_or(mode == Mode::Throttle),
+            trailing: options.trailing,
+            handler: RefCell::new(handler),
+            state: RefCell::new(State {
+                timeout: None,
+                pending: None,
+                closure: None,
+            }),
+        });
+
+        // The timeout only holds a weak reference to the timer as
+        // the timer is owned by the event listener.
+        let weak = Rc::downgrade(&timer);
+        timer.state.borrow_mut().closure = Some(Closure::wrap(Box::new(move || {
+            if let Some(timer) = Weak::upgrade(&weak) {
+                timer.expire()
+            }
+        }) as Box<dyn FnMut()>));
+
+        Self(timer)
+    }
+
+    fn call(&self, event: T) {
+        self.0.call(event)
+    }
+}
+
+impl<T, F> Drop for TimerHandle<T, F> {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.0.state.try_borrow_mut() {
+            if let (Some(timeout), Some(window)) = (state.timeout.take(), web_sys::window()) {
+                window.clear_timeout_with_handle(timeout);
+            }
+            state.pending = None;
+        }
+    }
+}
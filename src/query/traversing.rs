@@ -99,7 +99,33 @@ impl Element {
     }
 
     // TODO: .odd()
-    // TODO: .offsetParent()
+
+    /// Return the closest positioned ancestor, or the document
+    /// element.
+    pub fn offset_parent(&self) -> Option<Self> {
+        let mut parent = self
+            .dyn_ref::<web_sys::HtmlElement>()
+            .ok()
+            .and_then(|html| html.offset_parent())
+            .map(Self::from);
+        while let Some(element) = parent.as_ref() {
+            if element.local_name() == "html"
+                || element.css("position").ok().as_deref() != Some("static")
+            {
+                break;
+            }
+            parent = element
+                .dyn_ref::<web_sys::HtmlElement>()
+                .ok()
+                .and_then(|html| html.offset_parent())
+                .map(Self::from);
+        }
+        parent.or_else(|| {
+            self.owner_document()
+                .and_then(|document| document.document_element())
+                .map(Self::from)
+        })
+    }
 
     pub fn parent(&self) -> Option<Self> {
         self.parent_element().map(Into::into)
@@ -142,7 +168,7 @@ impl Collection {
     }
 
     pub fn first(&self) -> Option<Element> {
-        self.0.front().map(Clone::clone)
+        self.0.front().cloned()
     }
 
     pub fn has(&self, selectors: &str) -> Result<Collection, Error> {
@@ -160,7 +186,7 @@ impl Collection {
     }
 
     pub fn last(&self) -> Option<Element> {
-        self.0.back().map(Clone::clone)
+        self.0.back().cloned()
     }
 
     pub fn next(&self, selectors: Option<&str>) -> Result<Collection, Error> {
@@ -169,6 +195,12 @@ impl Collection {
             .collect()
     }
 
+    pub fn offset_parent(&self) -> Collection {
+        self.iter()
+            .filter_map(|elem| elem.offset_parent())
+            .collect()
+    }
+
     pub fn parent(&self) -> Collection {
         self.iter().filter_map(|elem| elem.parent()).collect()
     }
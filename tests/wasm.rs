@@ -1,6 +1,12 @@
 wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
 
-use std::{collections::HashMap, convert::TryFrom};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    convert::TryFrom,
+    rc::Rc,
+};
+use wasm_bindgen::JsCast;
 use wasm_bindgen_test::*;
 use web_sys::{DomParser, SupportedType};
 use web_sys_query::{self as query, query};
@@ -37,9 +43,24 @@ const HTML5_DOC: &str = r#"
   </div>
 </form>
 
+<ul id="list">
+  <li class="item">1</li>
+  <li>2</li>
+</ul>
+
 </body>
 </html>"#;
 
+async fn sleep(ms: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
 fn parse_document(source: &str) -> query::Document {
     console_log::init_with_level(log::Level::Info).ok();
 
@@ -75,7 +96,7 @@ fn test_order() {
     let matching = document.find("*").unwrap();
     console_log!("order: {:?}", matching);
 
-    let zero = matching.get(0).unwrap();
+    let zero = matching.front().unwrap();
     assert_eq!(zero.local_name(), "html", "{:?}", matching);
     let five = matching.get(4).unwrap();
     assert_eq!(five.local_name(), "h1", "{:?}", matching);
@@ -94,7 +115,7 @@ fn test_query_document() {
     let matching = query!(document, "title").unwrap();
     console_log!("query_document: {:?}", matching);
 
-    let title = matching.get(0).unwrap();
+    let title = matching.front().unwrap();
     assert_eq!(title.text().unwrap(), "Page Title");
 }
 
@@ -102,7 +123,7 @@ fn test_query_document() {
 fn test_query() {
     let matching = query!("*").unwrap();
     console_log!("query: {:?}", matching);
-    assert!(matching.len() > 0);
+    assert!(!matching.is_empty());
 }
 
 #[wasm_bindgen_test]
@@ -133,3 +154,952 @@ fn test_form_collection() {
     console_log!("form_collection: {:?}", collection);
     assert_eq!(single, collection);
 }
+
+#[wasm_bindgen_test]
+fn test_prop() {
+    let document = parse_document(HTML5_DOC);
+    let checkbox = document.find("#f").unwrap().first().unwrap();
+    let select = document.find("select").unwrap().first().unwrap();
+
+    assert_eq!(checkbox.prop_bool("checked"), Some(false));
+    checkbox.set_prop("checked", true).unwrap();
+    assert_eq!(checkbox.prop_bool("checked"), Some(true));
+    assert!(checkbox.attr("checked").is_none());
+    checkbox.set_prop("indeterminate", true).unwrap();
+    assert_eq!(checkbox.prop_bool("indeterminate"), Some(true));
+
+    assert_eq!(select.prop_f64("selectedIndex"), Some(0.0));
+    select.set_prop("selectedIndex", 2).unwrap();
+    assert_eq!(select.prop_str("value").as_deref(), Some("7"));
+
+    let items = document.find("li").unwrap();
+    items.set_prop("custom", "x").unwrap();
+    let values = items.prop("custom");
+    assert_eq!(values.len(), 2);
+    assert!(values
+        .iter()
+        .all(|value| value.as_string().as_deref() == Some("x")));
+    items.remove_prop("custom").unwrap();
+    assert!(items
+        .first()
+        .unwrap()
+        .prop("custom")
+        .unwrap()
+        .is_undefined());
+}
+
+#[wasm_bindgen_test]
+fn test_val() {
+    let document = parse_document(HTML5_DOC);
+    let input = document.find("#a").unwrap().first().unwrap();
+    let select = document.find("select").unwrap().first().unwrap();
+    let item = document.find("li").unwrap().first().unwrap();
+    let div = document.find("div").unwrap().first().unwrap();
+
+    assert_eq!(input.val().unwrap(), "1");
+    input.set_val("10").unwrap();
+    assert_eq!(input.val_i32().unwrap(), 10);
+    assert_eq!(select.val().unwrap(), "5");
+    assert_eq!(select.val_multiple().unwrap(), vec!["5"]);
+    assert!(div.val().is_err());
+    assert!(div.set_val("1").is_err());
+
+    item.set_val_i32(3).unwrap();
+    assert_eq!(item.val().unwrap(), "3");
+    assert_eq!(item.val_f64().unwrap(), 3.0);
+
+    let multiple = document
+        .create(
+            r#"<select multiple>
+                <option value="a" selected>A</option>
+                <option value="b">B</option>
+                <option value="c" selected>C</option>
+            </select>"#,
+        )
+        .unwrap()
+        .first()
+        .unwrap();
+    assert_eq!(multiple.val().unwrap(), "a");
+    assert_eq!(multiple.val_multiple().unwrap(), vec!["a", "c"]);
+    multiple.set_val_multiple(&["b", "c"]).unwrap();
+    assert_eq!(multiple.val_multiple().unwrap(), vec!["b", "c"]);
+    assert!(input.set_val_multiple(&["1"]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_event_listeners() {
+    let document = parse_document(HTML5_DOC);
+    let hero = document.find("#hero").unwrap().first().unwrap();
+    let count = Rc::new(Cell::new(0));
+
+    let mut listeners = (0..2)
+        .map(|_| {
+            let count = count.clone();
+            hero.on(query::Event::Click, move |_| count.set(count.get() + 1))
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let html = hero.dyn_ref::<web_sys::HtmlElement>().unwrap();
+    html.click();
+    assert_eq!(count.get(), 2);
+
+    listeners.pop().unwrap().off();
+    html.click();
+    assert_eq!(count.get(), 3);
+
+    listeners.pop().unwrap().forget();
+    html.click();
+    assert_eq!(count.get(), 4);
+
+    hero.off(query::Event::Click).unwrap();
+    html.click();
+    assert_eq!(count.get(), 4);
+}
+
+#[wasm_bindgen_test]
+fn test_event_delegate() {
+    let document = parse_document(HTML5_DOC);
+    let list = document.find("#list").unwrap().first().unwrap();
+    let matched = Rc::new(RefCell::new(vec![]));
+
+    let listener = {
+        let matched = matched.clone();
+        document
+            .delegate(query::Event::Click, "li.item", move |event| {
+                matched
+                    .borrow_mut()
+                    .push(event.current_target().unwrap().html())
+            })
+            .unwrap()
+    };
+
+    list.set_html(r#"<li class="item">3</li><li>4</li><li class="item"><b>5</b></li>"#);
+    for item in list.find("li, b").unwrap() {
+        item.dyn_ref::<web_sys::HtmlElement>().unwrap().click();
+    }
+    assert_eq!(*matched.borrow(), vec!["3", "<b>5</b>", "<b>5</b>"]);
+
+    drop(listener);
+    list.children(None)
+        .unwrap()
+        .first()
+        .unwrap()
+        .dyn_ref::<web_sys::HtmlElement>()
+        .unwrap()
+        .click();
+    assert_eq!(matched.borrow().len(), 3);
+}
+
+#[wasm_bindgen_test]
+fn test_event_one() {
+    let document = parse_document(HTML5_DOC);
+    let items = document.find("#list li").unwrap();
+    let count = Rc::new(Cell::new(0));
+    let click = |items: &query::Collection| {
+        for item in items.iter() {
+            item.dyn_ref::<web_sys::HtmlElement>().unwrap().click();
+            item.dyn_ref::<web_sys::HtmlElement>().unwrap().click();
+        }
+    };
+
+    let counter = count.clone();
+    let _once = items
+        .one(query::Event::Click, move |_| counter.set(counter.get() + 1))
+        .unwrap();
+    click(&items);
+    assert_eq!(count.get(), 2);
+
+    let counter = count.clone();
+    let _once = items
+        .one_for_all(query::Event::Click, move |_| counter.set(counter.get() + 1))
+        .unwrap();
+    click(&items);
+    assert_eq!(count.get(), 3);
+
+    let item = items.first().unwrap();
+    let (counter, target) = (count.clone(), item.clone());
+    let _once = item
+        .one(query::Event::Click, move |_| {
+            counter.set(counter.get() + 1);
+            target.trigger(query::Event::Click).unwrap();
+        })
+        .unwrap();
+    item.trigger(query::Event::Click).unwrap();
+    assert_eq!(count.get(), 4);
+
+    let (counter, others) = (count.clone(), items.clone());
+    let _once = items
+        .one_for_all(query::Event::Click, move |_| {
+            counter.set(counter.get() + 1);
+            others.trigger(query::Event::Click).unwrap();
+        })
+        .unwrap();
+    item.trigger(query::Event::Click).unwrap();
+    assert_eq!(count.get(), 5);
+}
+
+#[wasm_bindgen_test]
+fn test_event_nested() {
+    let document = parse_document(HTML5_DOC);
+    let items = document.find("#list li").unwrap();
+    let clicked = Rc::new(RefCell::new(vec![]));
+
+    let _listener = {
+        let (clicked, others) = (clicked.clone(), items.clone());
+        items
+            .on(query::Event::Click, move |event| {
+                let item = query::Element::try_from(event).unwrap();
+                clicked.borrow_mut().push(item.html());
+                if item.html() == "1" {
+                    others.last().unwrap().trigger(query::Event::Click).unwrap();
+                    clicked.borrow_mut().push("1 done".to_string());
+                }
+            })
+            .unwrap()
+    };
+
+    // The shared handler is busy, so the nested click on the second
+    // item is deferred until the first call returned.
+    items.first().unwrap().trigger(query::Event::Click).unwrap();
+    assert_eq!(*clicked.borrow(), vec!["1", "1 done", "2"]);
+}
+
+#[wasm_bindgen_test]
+fn test_event_hover_ready() {
+    let document = parse_document(HTML5_DOC);
+    let hero = document.find("#hero").unwrap();
+    let state = Rc::new(RefCell::new(vec![]));
+
+    let (enter, leave) = (state.clone(), state.clone());
+    let _hover = hero
+        .hover(
+            move |_| enter.borrow_mut().push("enter"),
+            move |_| leave.borrow_mut().push("leave"),
+        )
+        .unwrap();
+    for name in &["mouseenter", "mouseleave", "mouseenter"] {
+        let event = web_sys::Event::new(name).unwrap();
+        hero.first().unwrap().dispatch_event(&event).unwrap();
+    }
+    assert_eq!(*state.borrow(), vec!["enter", "leave", "enter"]);
+
+    let ready = state.clone();
+    query::Document::new()
+        .unwrap()
+        .ready(move || ready.borrow_mut().push("ready"))
+        .unwrap()
+        .forget();
+    assert_eq!(state.borrow().last(), Some(&"ready"));
+}
+
+#[wasm_bindgen_test]
+fn test_event_custom() {
+    let document = parse_document(HTML5_DOC);
+    let hero = document.find("#hero").unwrap().first().unwrap();
+    let count = Rc::new(Cell::new(0));
+
+    assert_eq!(
+        query::Event::from_name("pointerdown"),
+        query::Event::PointerDown
+    );
+    assert_eq!(query::Event::PointerDown.name(), Some("pointerdown"));
+    assert_eq!(
+        query::Event::from_name("app:saved"),
+        query::Event::custom("app:saved")
+    );
+
+    let counter = count.clone();
+    let _listener = hero
+        .on(query::Event::custom("app:saved"), move |_| {
+            counter.set(counter.get() + 1)
+        })
+        .unwrap();
+    let event = web_sys::Event::new("app:saved").unwrap();
+    hero.dispatch_event(&event).unwrap();
+    assert_eq!(count.get(), 1);
+}
+
+#[wasm_bindgen_test]
+fn test_event_trigger() {
+    let document = parse_document(HTML5_DOC);
+    let list = document.find("#list").unwrap().first().unwrap();
+    let item = list.children(None).unwrap().first().unwrap();
+    let state = Rc::new(RefCell::new(vec![]));
+
+    let parent = state.clone();
+    let _parent = list
+        .on(query::Event::Click, move |_| {
+            parent.borrow_mut().push("list")
+        })
+        .unwrap();
+    let child = state.clone();
+    let _child = item
+        .on(query::Event::Click, move |_| {
+            child.borrow_mut().push("item")
+        })
+        .unwrap();
+
+    assert!(item.trigger(query::Event::Click).unwrap());
+    assert_eq!(*state.borrow(), vec!["item", "list"]);
+
+    item.trigger_handler(query::Event::Click).unwrap();
+    assert_eq!(*state.borrow(), vec!["item", "list", "item"]);
+
+    let detail = Rc::new(RefCell::new(None));
+    let refresh = detail.clone();
+    let _custom = list
+        .on(query::Event::custom("app:refresh"), move |event| {
+            let event = event.dyn_into::<web_sys::CustomEvent>().unwrap();
+            *refresh.borrow_mut() = event.detail().as_string();
+        })
+        .unwrap();
+    item.trigger_custom("app:refresh", "reload").unwrap();
+    assert_eq!(detail.borrow().as_deref(), Some("reload"));
+}
+
+#[wasm_bindgen_test]
+fn test_event_namespaces() {
+    let document = parse_document(HTML5_DOC);
+    let items = document.find("#list li").unwrap();
+    let item = items.first().unwrap();
+    let state = Rc::new(RefCell::new(vec![]));
+
+    let listeners = vec![
+        ("click.dropdown", "dropdown"),
+        ("click.dropdown.menu", "menu"),
+        ("click.tooltip", "tooltip"),
+        ("mouseenter.dropdown", "enter"),
+    ]
+    .into_iter()
+    .map(|(event, name)| {
+        let state = state.clone();
+        items
+            .on(event, move |_| state.borrow_mut().push(name))
+            .unwrap()
+    })
+    .collect::<Vec<_>>();
+
+    item.trigger(query::Event::Click).unwrap();
+    assert_eq!(state.borrow().len(), 3);
+
+    items.off(query::Event::Click.namespace("menu")).unwrap();
+    state.borrow_mut().clear();
+    item.trigger(query::Event::Click).unwrap();
+    assert_eq!(*state.borrow(), vec!["dropdown", "tooltip"]);
+
+    items.off(".dropdown").unwrap();
+    state.borrow_mut().clear();
+    item.trigger(query::Event::Click).unwrap();
+    item.trigger(query::Event::MouseEnter).unwrap();
+    assert_eq!(*state.borrow(), vec!["tooltip"]);
+
+    drop(listeners);
+}
+
+#[wasm_bindgen_test]
+fn test_event_typed() {
+    let document = parse_document(HTML5_DOC);
+    let input = document.find("#a").unwrap().first().unwrap();
+    let keys = Rc::new(RefCell::new(vec![]));
+
+    let typed = keys.clone();
+    let _typed = input
+        .on_key_down(move |event: web_sys::KeyboardEvent| typed.borrow_mut().push(event.key()))
+        .unwrap();
+    let context = keys.clone();
+    let _context = input
+        .on(query::Event::KeyDown, move |event| {
+            let event = query::EventContext::from(event);
+            assert_eq!(event.target().unwrap().id(), "a");
+            assert!(event.ctrl_key());
+            context.borrow_mut().push(event.key().unwrap());
+        })
+        .unwrap();
+
+    let init = web_sys::KeyboardEventInit::new();
+    init.set_key("k");
+    init.set_ctrl_key(true);
+    let event =
+        web_sys::KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init).unwrap();
+    input.dispatch_event(&event).unwrap();
+    assert_eq!(*keys.borrow(), vec!["k", "k"]);
+}
+
+#[wasm_bindgen_test]
+fn test_event_trigger_typed() {
+    let document = parse_document(HTML5_DOC);
+    let input = document.find("#a").unwrap().first().unwrap();
+    let keys = Rc::new(RefCell::new(vec![]));
+
+    let typed = keys.clone();
+    let _typed = input
+        .on_key_down(move |event: web_sys::KeyboardEvent| typed.borrow_mut().push(event.key()))
+        .unwrap();
+    input.trigger(query::Event::KeyDown).unwrap();
+    input.trigger_handler(query::Event::KeyDown).unwrap();
+    assert_eq!(*keys.borrow(), vec!["", ""]);
+
+    let event = web_sys::Event::new("keydown").unwrap();
+    input.dispatch_event(&event).unwrap();
+    assert_eq!(keys.borrow().len(), 2);
+
+    let resized = Rc::new(Cell::new(false));
+    let typed = resized.clone();
+    let _resize = input
+        .on_resize(move |_: web_sys::UiEvent| typed.set(true))
+        .unwrap();
+    input.trigger(query::Event::Resize).unwrap();
+    assert!(resized.get());
+    assert_eq!(query::Event::Click.interface(), "MouseEvent");
+    assert_eq!(query::Event::custom("x").interface(), "Event");
+}
+
+#[wasm_bindgen_test]
+fn test_event_options() {
+    let document = parse_document(HTML5_DOC);
+    let list = document.find("#list").unwrap().first().unwrap();
+    let item = list.find(".item").unwrap().first().unwrap();
+    let order = Rc::new(RefCell::new(vec![]));
+
+    let bubble = order.clone();
+    let _bubble = list
+        .on(query::Event::Click, move |_| {
+            bubble.borrow_mut().push("bubble")
+        })
+        .unwrap();
+    let capture = order.clone();
+    let _capture = list
+        .on_with(
+            query::Event::Click,
+            &query::ListenerOptions::new().capture(true).passive(true),
+            move |_| capture.borrow_mut().push("capture"),
+        )
+        .unwrap();
+    let target = order.clone();
+    let _target = item
+        .on_with(
+            query::Event::Click,
+            &query::ListenerOptions::new().once(true),
+            move |_| target.borrow_mut().push("target"),
+        )
+        .unwrap();
+
+    let html = item.dyn_ref::<web_sys::HtmlElement>().unwrap();
+    html.click();
+    assert_eq!(*order.borrow(), vec!["capture", "target", "bubble"]);
+
+    order.borrow_mut().clear();
+    let controller = web_sys::AbortController::new().unwrap();
+    let signal = order.clone();
+    let _signal = item
+        .on_with(
+            query::Event::Click,
+            &query::ListenerOptions::new().signal(&controller.signal()),
+            move |_| signal.borrow_mut().push("signal"),
+        )
+        .unwrap();
+    html.click();
+    controller.abort();
+    html.click();
+    assert_eq!(
+        *order.borrow(),
+        vec!["capture", "signal", "bubble", "capture", "bubble"]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_document_events() {
+    let document = parse_document(HTML5_DOC);
+    let input = document.find("#a").unwrap().first().unwrap();
+    let keys = Rc::new(RefCell::new(vec![]));
+
+    let global = keys.clone();
+    let _global = document
+        .on_key_down(move |event: web_sys::KeyboardEvent| global.borrow_mut().push(event.key()))
+        .unwrap();
+    let visible = keys.clone();
+    let _visible = document
+        .on(query::Event::VisibilityChange, move |_| {
+            visible.borrow_mut().push("visible".to_string())
+        })
+        .unwrap();
+
+    let init = web_sys::KeyboardEventInit::new();
+    init.set_key("k");
+    init.set_bubbles(true);
+    let event =
+        web_sys::KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init).unwrap();
+    input.dispatch_event(&event).unwrap();
+    document.trigger(query::Event::VisibilityChange).unwrap();
+    assert_eq!(*keys.borrow(), vec!["k", "visible"]);
+
+    document.off(query::Event::KeyDown).unwrap();
+    input.dispatch_event(&event).unwrap();
+    document
+        .trigger_handler(query::Event::VisibilityChange)
+        .unwrap();
+    assert_eq!(*keys.borrow(), vec!["k", "visible", "visible"]);
+}
+
+#[wasm_bindgen_test]
+fn test_shortcuts() {
+    let document = parse_document(HTML5_DOC);
+    let list = document.find("#list").unwrap().first().unwrap();
+    let item = list.find(".item").unwrap().first().unwrap();
+    let input = document.find("#a").unwrap().first().unwrap();
+    let calls = Rc::new(RefCell::new(vec![]));
+
+    let key_down = |target: &query::Element, key: &str, ctrl: bool, shift: bool| {
+        let init = web_sys::KeyboardEventInit::new();
+        init.set_key(key);
+        init.set_ctrl_key(ctrl);
+        init.set_shift_key(shift);
+        init.set_bubbles(true);
+        init.set_cancelable(true);
+        let event =
+            web_sys::KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init).unwrap();
+        target.dispatch_event(&event).unwrap()
+    };
+
+    let global = calls.clone();
+    let _global = document
+        .shortcut("Ctrl+Shift+K", move |_| global.borrow_mut().push("global"))
+        .unwrap();
+    let sequence = calls.clone();
+    let _sequence = list
+        .shortcut("g i", move |_| sequence.borrow_mut().push("sequence"))
+        .unwrap();
+    assert!(document.shortcut("Hyper+K", |_| ()).is_err());
+
+    assert!(!key_down(&item, "K", true, true));
+    assert!(key_down(&item, "K", true, false));
+    assert!(key_down(&input, "K", true, true));
+    assert_eq!(*calls.borrow(), vec!["global"]);
+
+    key_down(&item, "g", false, false);
+    key_down(&item, "i", false, false);
+    key_down(&item, "g", false, false);
+    key_down(&item, "x", false, false);
+    key_down(&item, "i", false, false);
+    key_down(&input, "g", false, false);
+    key_down(&input, "i", false, false);
+    assert_eq!(*calls.borrow(), vec!["global", "sequence"]);
+}
+
+#[wasm_bindgen_test]
+fn test_window() {
+    let window = query::Window::new().unwrap();
+    let count = Rc::new(Cell::new(0));
+
+    let resize = count.clone();
+    let _resize = window
+        .on(query::Event::Resize, move |_| resize.set(resize.get() + 1))
+        .unwrap();
+    let online = count.clone();
+    let _online = window
+        .on_online(move |_| online.set(online.get() + 10))
+        .unwrap();
+    window.trigger(query::Event::Resize).unwrap();
+    window.trigger_handler(query::Event::Online).unwrap();
+    assert_eq!(count.get(), 11);
+
+    window.off(query::Event::Resize).unwrap();
+    window.trigger(query::Event::Resize).unwrap();
+    assert_eq!(count.get(), 11);
+
+    let detail = Rc::new(RefCell::new(vec![]));
+    let custom = detail.clone();
+    let _custom = window
+        .on("app:ready", move |event| {
+            let event = event.dyn_into::<web_sys::CustomEvent>().unwrap();
+            custom
+                .borrow_mut()
+                .push(event.detail().as_string().unwrap());
+        })
+        .unwrap();
+    assert!(window.trigger_custom("app:ready", "window").unwrap());
+    let options = query::TriggerOptions::new().detail("options");
+    window
+        .trigger_with(query::Event::custom("app:ready"), &options)
+        .unwrap();
+    assert_eq!(*detail.borrow(), vec!["window", "options"]);
+
+    let (width, height) = window.inner_size().unwrap();
+    assert!(width > 0.0 && height > 0.0);
+    assert!(window.device_pixel_ratio() > 0.0);
+    assert!(window.document().is_ok());
+}
+
+#[wasm_bindgen_test]
+async fn test_event_timing() {
+    use std::time::Duration;
+
+    let document = parse_document(HTML5_DOC);
+    let hero = document.find("#hero").unwrap().first().unwrap();
+    let calls = Rc::new(RefCell::new(vec![]));
+
+    let debounced = calls.clone();
+    let _debounced = hero
+        .on(
+            query::Event::Click,
+            query::debounce(Duration::from_millis(20), move |_| {
+                debounced.borrow_mut().push("debounce")
+            }),
+        )
+        .unwrap();
+    let throttled = calls.clone();
+    let _throttled = hero
+        .on(
+            query::Event::Click,
+            query::throttle(Duration::from_millis(20), move |_| {
+                throttled.borrow_mut().push("throttle")
+            }),
+        )
+        .unwrap();
+
+    for _ in 0..3 {
+        hero.trigger(query::Event::Click).unwrap();
+    }
+    assert_eq!(*calls.borrow(), vec!["throttle"]);
+    sleep(50).await;
+    assert_eq!(*calls.borrow(), vec!["throttle", "throttle", "debounce"]);
+
+    calls.borrow_mut().clear();
+    hero.trigger(query::Event::Click).unwrap();
+    hero.off(query::Event::Click).unwrap();
+    sleep(50).await;
+    assert_eq!(*calls.borrow(), vec!["throttle"]);
+}
+
+#[cfg(feature = "futures")]
+#[wasm_bindgen_test]
+async fn test_event_stream() {
+    use futures_core::Stream;
+    use std::{future::poll_fn, pin::Pin};
+
+    let document = parse_document(HTML5_DOC);
+    let hero = document.find("#hero").unwrap().first().unwrap();
+
+    let next = hero.next_event(query::Event::Click);
+    let mut stream = hero.events(query::Event::Click).unwrap();
+    hero.trigger(query::Event::Click).unwrap();
+    hero.trigger(query::Event::Click).unwrap();
+
+    assert_eq!(next.await.unwrap().type_(), "click");
+    for _ in 0..2 {
+        let event = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await;
+        assert_eq!(event.unwrap().type_(), "click");
+    }
+
+    hero.off(query::Event::Click).unwrap();
+    assert!(poll_fn(|cx| Pin::new(&mut stream).poll_next(cx))
+        .await
+        .is_none());
+}
+
+#[wasm_bindgen_test]
+fn test_insert() {
+    let document = parse_document(HTML5_DOC);
+    let list = document.find("#list").unwrap().first().unwrap();
+    let hero = document.find("#hero").unwrap().first().unwrap();
+
+    list.append("<li>3</li>").unwrap();
+    list.prepend(query::Content::text("<li>")).unwrap();
+    assert_eq!(list.children(None).unwrap().len(), 3);
+    assert_eq!(list.first_child().unwrap().text_content().unwrap(), "<li>");
+
+    let span = query::Element::from(document.create_element("span").unwrap());
+    let paragraphs = document.find("p").unwrap();
+    paragraphs.append(&span).unwrap();
+    assert_eq!(document.find("p > span").unwrap().len(), 2);
+    let last = paragraphs.last().unwrap().find("span").unwrap();
+    assert!(last.first().unwrap().is_same_node(Some(&span)));
+
+    hero.insert_after("#list").unwrap();
+    assert!(list.next(None).unwrap().unwrap().is_same_node(Some(&hero)));
+    hero.insert_before(&list).unwrap();
+    assert!(list.prev(None).unwrap().unwrap().is_same_node(Some(&hero)));
+
+    paragraphs.before(query::Content::text("x")).unwrap();
+    span.append_to("li").unwrap();
+    assert_eq!(document.find("li > span").unwrap().len(), 3);
+}
+
+#[wasm_bindgen_test]
+fn test_create() {
+    let document = parse_document(HTML5_DOC);
+    let list = document.find("#list").unwrap().first().unwrap();
+
+    let items = document
+        .create("<li class='x'>hi</li> <li>there</li>")
+        .unwrap();
+    assert_eq!(items.len(), 2);
+    assert!(items.first().unwrap().has_class("x"));
+    let rows = document.create("<tr><td>1</td></tr>").unwrap();
+    assert_eq!(rows.first().unwrap().local_name(), "tr");
+    list.append(items).unwrap();
+    assert_eq!(list.children(None).unwrap().len(), 4);
+
+    let items = document.create("<li>3</li><li>4</li>").unwrap();
+    items.append_to("#list").unwrap();
+    assert_eq!(list.children(None).unwrap().len(), 6);
+    let item = document.create("<li>0</li>").unwrap().first().unwrap();
+    item.insert_before("#list li:first-child").unwrap();
+    let children = list.children(None).unwrap();
+    assert_eq!(children.len(), 7);
+    assert_eq!(children.first().unwrap().html(), "0");
+
+    let item = document
+        .build("li")
+        .class("x")
+        .attr("data-id", "3")
+        .text("hi")
+        .child(document.build("span").id("s").text("!").build().unwrap())
+        .build()
+        .unwrap();
+    assert_eq!(item.attr("data-id").as_deref(), Some("3"));
+    assert_eq!(item.html(), "hi<span id=\"s\">!</span>");
+    item.append_to(&list).unwrap();
+    assert_eq!(document.find("li.x").unwrap().len(), 2);
+
+    assert!(document.build("li").attr("", "x").build().is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_remove() {
+    let document = parse_document(HTML5_DOC);
+    let list = document.find("#list").unwrap().first().unwrap();
+    let item = list.find(".item").unwrap().first().unwrap();
+    let count = Rc::new(Cell::new(0));
+
+    let clicks = count.clone();
+    item.on(query::Event::Click, move |_| clicks.set(clicks.get() + 1))
+        .unwrap()
+        .forget();
+    item.set_data("id", 1);
+    assert_eq!(item.data("id").unwrap().as_f64(), Some(1.0));
+
+    item.detach();
+    assert_eq!(list.children(None).unwrap().len(), 1);
+    list.prepend(&item).unwrap();
+    item.trigger(query::Event::Click).unwrap();
+    assert_eq!(count.get(), 1);
+    assert!(item.data("id").is_some());
+
+    item.remove();
+    assert_eq!(list.children(None).unwrap().len(), 1);
+    item.trigger(query::Event::Click).unwrap();
+    assert_eq!(count.get(), 1);
+    assert!(item.data("id").is_none());
+
+    let paragraphs = document.find("p").unwrap();
+    paragraphs.replace_with("<div class='p'></div>").unwrap();
+    assert_eq!(document.find("p").unwrap().len(), 0);
+    assert_eq!(document.find("div.p").unwrap().len(), 2);
+
+    let hero = document.find("#hero").unwrap().first().unwrap();
+    hero.replace_all("div.p").unwrap();
+    assert_eq!(document.find("div.p").unwrap().len(), 0);
+    assert_eq!(document.find("h1").unwrap().len(), 2);
+
+    list.empty();
+    assert_eq!(list.child_nodes().length(), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_wrap() {
+    let document = parse_document(HTML5_DOC);
+    let items = document.find("#list > li").unwrap();
+
+    items
+        .wrap("<div class='outer'><span class='inner'></span></div>")
+        .unwrap();
+    assert_eq!(
+        document.find("div.outer > span.inner > li").unwrap().len(),
+        2
+    );
+    items.unwrap(Some("span")).unwrap();
+    items.unwrap(None).unwrap();
+    assert_eq!(document.find("#list > li").unwrap().len(), 2);
+    assert_eq!(document.find(".outer, .inner").unwrap().len(), 0);
+
+    items.wrap_all("<div class='all'></div>").unwrap();
+    assert_eq!(document.find("#list > div.all > li").unwrap().len(), 2);
+    items.unwrap(Some("p")).unwrap();
+    assert_eq!(document.find("div.all").unwrap().len(), 1);
+    items.unwrap(None).unwrap();
+    assert_eq!(document.find("div.all").unwrap().len(), 0);
+
+    let hero = document.find("#hero").unwrap().first().unwrap();
+    hero.wrap_inner("<em></em>").unwrap();
+    assert_eq!(hero.html(), "<em>This is a Heading</em>");
+    assert!(hero.wrap(query::Content::text("x")).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_clone_node() {
+    let document = parse_document(HTML5_DOC);
+    let list = document.find("#list").unwrap().first().unwrap();
+    let item = list.find(".item").unwrap().first().unwrap();
+    let clicks = Rc::new(RefCell::new(vec![]));
+
+    let handler = clicks.clone();
+    list.on(query::Event::Click, move |event| {
+        let target = query::Element::try_from(event).unwrap();
+        handler.borrow_mut().push(target.local_name());
+    })
+    .unwrap()
+    .forget();
+    let handler = clicks.clone();
+    item.on("click.item", move |_| {
+        handler.borrow_mut().push("item".into())
+    })
+    .unwrap()
+    .forget();
+    list.set_data("id", "list");
+    item.set_data("id", "item");
+
+    let plain = list.clone_node(false, false).unwrap();
+    assert!(!plain.is_same_node(Some(&list)));
+    assert_eq!(plain.children(None).unwrap().len(), 2);
+    assert!(plain.data("id").is_none());
+    plain.trigger(query::Event::Click).unwrap();
+    assert!(clicks.borrow().is_empty());
+
+    let copy = list.clone_node(true, false).unwrap();
+    assert_eq!(copy.data("id").unwrap().as_string().unwrap(), "list");
+    let copy_item = copy.find(".item").unwrap().first().unwrap();
+    assert!(copy_item.data("id").is_none());
+    copy_item.trigger(query::Event::Click).unwrap();
+    assert_eq!(*clicks.borrow(), vec!["li"]);
+
+    clicks.borrow_mut().clear();
+    let shallow = list.clone_node(false, true).unwrap();
+    let shallow_item = shallow.find(".item").unwrap().first().unwrap();
+    assert!(shallow_item.data("id").is_none());
+    shallow_item.trigger(query::Event::Click).unwrap();
+    assert!(clicks.borrow().is_empty());
+
+    let deep = list.clone_node(true, true).unwrap();
+    let deep_item = deep.find(".item").unwrap().first().unwrap();
+    assert_eq!(deep_item.data("id").unwrap().as_string().unwrap(), "item");
+    deep_item.trigger(query::Event::Click).unwrap();
+    assert_eq!(*clicks.borrow(), vec!["item", "li"]);
+
+    deep_item.off(".item").unwrap();
+    item.trigger(query::Event::Click).unwrap();
+    assert_eq!(*clicks.borrow(), vec!["item", "li", "item", "li"]);
+}
+
+#[wasm_bindgen_test]
+fn test_css() {
+    let document = query::Document::new().unwrap();
+    let body = document.find("body").unwrap();
+    let div = document.build("div").build().unwrap();
+    div.append_to(&body).unwrap();
+
+    div.set_css("width", "10").unwrap();
+    div.set_css("zIndex", "3").unwrap();
+    div.set_css("opacity", "0.5").unwrap();
+    assert_eq!(div.css("width").unwrap(), "10px");
+    assert_eq!(div.css("z-index").unwrap(), "3");
+    assert_eq!(div.css("opacity").unwrap(), "0.5");
+    assert_eq!(
+        div.attr("style").unwrap(),
+        "width: 10px; z-index: 3; opacity: 0.5;"
+    );
+
+    let divs = query::Collection::from(vec![div.clone(), div.clone_node(false, false).unwrap()]);
+    divs.set_css_map(vec![("height", "2em"), ("width", "")])
+        .unwrap();
+    assert_eq!(divs.css("height"), vec!["32px", "2em"]);
+    assert_eq!(
+        div.attr("style").unwrap(),
+        "z-index: 3; opacity: 0.5; height: 2em;"
+    );
+
+    div.remove();
+}
+
+#[wasm_bindgen_test]
+fn test_dimensions() {
+    let document = query::Document::new().unwrap();
+    let body = document.find("body").unwrap();
+    let div = document.build("div").build().unwrap();
+    div.append_to(&body).unwrap();
+
+    div.set_css_map(vec![
+        ("display", "block"),
+        ("padding", "5px"),
+        ("border", "2px solid black"),
+        ("margin", "3px"),
+    ])
+    .unwrap();
+    div.set_width(100.0).unwrap();
+    div.set_height(50.0).unwrap();
+    assert_eq!(div.width(), 100.0);
+    assert_eq!(div.inner_width(), 110.0);
+    assert_eq!(div.outer_width(false), 114.0);
+    assert_eq!(div.outer_width(true), 120.0);
+    assert_eq!(div.height(), 50.0);
+    assert_eq!(div.outer_height(true), 70.0);
+
+    div.set_css("box-sizing", "border-box").unwrap();
+    div.set_width(100.0).unwrap();
+    assert_eq!(div.css("width").unwrap(), "114px");
+    assert_eq!(div.width(), 100.0);
+
+    let window = query::Window::new().unwrap();
+    assert!(window.width() > 0.0 && window.height() > 0.0);
+    assert!(document.height() >= window.height());
+
+    div.remove();
+}
+
+#[wasm_bindgen_test]
+fn test_offset() {
+    let document = query::Document::new().unwrap();
+    let body = document.find("body").unwrap();
+    let child = document.build("div").build().unwrap();
+    let container = document.build("div").child(&child).build().unwrap();
+    container.append_to(&body).unwrap();
+
+    container
+        .set_css_map(vec![
+            ("position", "absolute"),
+            ("top", "100"),
+            ("left", "50"),
+            ("border", "2px solid black"),
+            ("height", "20"),
+            ("overflow", "auto"),
+        ])
+        .unwrap();
+    child
+        .set_css_map(vec![("margin", "5"), ("height", "100")])
+        .unwrap();
+
+    assert_eq!(container.offset(), query::Offset::new(100.0, 50.0));
+    assert_eq!(child.offset(), query::Offset::new(107.0, 57.0));
+    assert_eq!(child.position().unwrap(), query::Offset::new(0.0, 0.0));
+    assert!(child
+        .offset_parent()
+        .unwrap()
+        .is_same_node(Some(&container)));
+    assert_eq!(container.offset_parent().unwrap().local_name(), "html");
+
+    container.set_scroll_top(10.0);
+    assert_eq!(container.scroll_top(), 10.0);
+    assert_eq!(child.offset(), query::Offset::new(97.0, 57.0));
+    assert_eq!(child.position().unwrap(), query::Offset::new(0.0, 0.0));
+    container.set_scroll_top(0.0);
+
+    child.set_offset(query::Offset::new(200.0, 60.0)).unwrap();
+    assert_eq!(child.css("position").unwrap(), "relative");
+    assert_eq!(child.offset(), query::Offset::new(200.0, 60.0));
+
+    child.scroll_into_view_with(
+        &query::ScrollOptions::new().block(web_sys::ScrollLogicalPosition::Center),
+    );
+    container.remove();
+}